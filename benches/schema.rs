@@ -45,4 +45,39 @@ fn bench_validation(b: &mut test::Bencher) {
     let compiled_schema = scope.compile_and_return(schema.clone(), true).ok().unwrap();
 
     b.iter(|| assert!(compiled_schema.validate(&schema).is_valid()));
-}
\ No newline at end of file
+}
+
+fn invalid_instance() -> Value {
+    from_str(r#"{
+        "type": 1,
+        "required": [],
+        "enum": [],
+        "properties": {
+            "a": { "minimum": "0", "maxLength": -1 },
+            "b": { "items": 1, "anyOf": [] }
+        },
+        "definitions": {
+            "c": { "pattern": 2, "dependencies": { "d": 3 } }
+        }
+    }"#).unwrap()
+}
+
+#[bench]
+fn bench_validation_invalid(b: &mut test::Bencher) {
+    let schema = read_schema();
+    let instance = invalid_instance();
+    let mut scope = json_schema::Scope::new();
+    let compiled_schema = scope.compile_and_return(schema.clone(), true).ok().unwrap();
+
+    b.iter(|| assert!(!compiled_schema.validate(&instance).is_valid()));
+}
+
+#[bench]
+fn bench_is_valid_invalid(b: &mut test::Bencher) {
+    let schema = read_schema();
+    let instance = invalid_instance();
+    let mut scope = json_schema::Scope::new();
+    let compiled_schema = scope.compile_and_return(schema.clone(), true).ok().unwrap();
+
+    b.iter(|| assert!(!compiled_schema.is_valid(&instance)));
+}
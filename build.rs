@@ -22,7 +22,31 @@ fn main() {
         .entry("properties")
         .entry("patternProperties")
         .entry("dependencies")
+        .entry("dependentSchemas")
         .entry("definitions")
+        .entry("$defs")
+        .entry("anyOf")
+        .entry("allOf")
+        .entry("oneOf")
+        .build(&mut file)
+        .unwrap();
+    write!(&mut file, ";\n").unwrap();
+
+    write!(&mut file, "static SCHEMA_KEYS: phf::Set<&'static str> = ").unwrap();
+    phf_codegen::Set::new()
+        .entry("additionalItems")
+        .entry("additionalProperties")
+        .entry("items")
+        .entry("prefixItems")
+        .entry("contains")
+        .entry("not")
+        .entry("if")
+        .entry("then")
+        .entry("else")
+        .entry("propertyNames")
+        .entry("unevaluatedProperties")
+        .entry("unevaluatedItems")
+        .entry("contentSchema")
         .entry("anyOf")
         .entry("allOf")
         .entry("oneOf")
@@ -35,6 +59,10 @@ fn main() {
         .entry("enum")
         .entry("required")
         .entry("type")
+        .entry("const")
+        .entry("examples")
+        .entry("dependentRequired")
+        .entry("errorMessage")
         .build(&mut file)
         .unwrap();
     write!(&mut file, ";\n").unwrap();
@@ -44,9 +72,32 @@ fn main() {
         .entry("definitions")
         .entry("$schema")
         .entry("id")
+        .entry("$id")
         .entry("default")
         .entry("description")
         .entry("format")
+        .entry("examples")
+        .entry("$comment")
+        .entry("readOnly")
+        .entry("writeOnly")
+        .entry("contentMediaType")
+        .entry("contentEncoding")
+        .entry("$defs")
+        .entry("$anchor")
+        .entry("$recursiveAnchor")
+        .entry("$dynamicAnchor")
+        .entry("$vocabulary")
+        .entry("deprecated")
+        .entry("contentSchema")
+        .entry("errorMessage")
+        .build(&mut file)
+        .unwrap();
+    write!(&mut file, ";\n").unwrap();
+
+    write!(&mut file, "static DEFERRED_KEYS: phf::Set<&'static str> = ").unwrap();
+    phf_codegen::Set::new()
+        .entry("unevaluatedProperties")
+        .entry("unevaluatedItems")
         .build(&mut file)
         .unwrap();
     write!(&mut file, ";\n").unwrap();
@@ -0,0 +1,156 @@
+//! Message catalogs used to render the errors in other languages than the English
+//! titles of the error types.
+
+use std::collections;
+use serde_json::{Value};
+
+use super::error::ValicoError;
+
+/// Message templates keyed by error code.
+///
+/// A template can refer to the parameters of the error in braces: `{path}`,
+/// `{detail}` and the ones returned by `ValicoError::get_params`, like `{limit}`.
+pub trait MessageCatalog: Send + Sync {
+    fn message(&self, code: &str) -> Option<&str>;
+
+    /// The template of the error, by default the message of its code. Catalogs
+    /// override it to phrase some errors depending on their parameters.
+    fn template(&self, error: &ValicoError) -> Option<&str> {
+        self.message(error.get_code())
+    }
+}
+
+/// The English messages. Errors without a template of their own, or whose template
+/// refers to parameters they don't have, are rendered with their titles.
+#[derive(Debug, Clone, Copy)]
+pub struct English;
+
+impl MessageCatalog for English {
+    fn message(&self, code: &str) -> Option<&str> {
+        let message = match code {
+            "wrong_type" => "The value must be of type {expected}, got {actual}",
+            "multiple_of" => "The value must be a multiple of {multiple_of}, got {actual}",
+            "maximum" => "The value must be at most {limit}, got {actual}",
+            "minimum" => "The value must be at least {limit}, got {actual}",
+            "max_length" => "The length must be at most {limit}, got {actual}",
+            "min_length" => "The length must be at least {limit}, got {actual}",
+            "pattern" => "The value must match the pattern {pattern}",
+            "max_items" => "The array must have at most {limit} items, got {actual}",
+            "min_items" => "The array must have at least {limit} items, got {actual}",
+            "max_properties" => "The object must have at most {limit} properties, got {actual}",
+            "min_properties" => "The object must have at least {limit} properties, got {actual}",
+            "required" => "The property {property} is required",
+            "enum" => "The value must be one of {allowed}",
+            "const" => "The value must be {expected}",
+            "format" => "The value must be a valid {format}: {detail}",
+            "min_contains" => "The array must contain at least {limit} matching items, got {actual}",
+            "max_contains" => "The array must contain at most {limit} matching items, got {actual}",
+            "reference_depth" => "References are nested deeper than {limit}",
+            _ => return None
+        };
+
+        Some(message)
+    }
+
+    fn template(&self, error: &ValicoError) -> Option<&str> {
+        let exclusive = error.get_params().get("exclusive").and_then(|exclusive| exclusive.as_bool()).unwrap_or(false);
+        match error.get_code() {
+            "maximum" if exclusive => Some("The value must be less than {limit}, got {actual}"),
+            "minimum" if exclusive => Some("The value must be greater than {limit}, got {actual}"),
+            code => self.message(code)
+        }
+    }
+}
+
+/// Catalogs loaded from translation files.
+impl MessageCatalog for collections::HashMap<String, String> {
+    fn message(&self, code: &str) -> Option<&str> {
+        self.get(code).map(|message| message.as_ref())
+    }
+}
+
+/// Renders the message of the error from the catalog, falling back to its title
+/// when the catalog has no message for its code or the message refers to
+/// parameters the error doesn't have.
+pub fn localize(error: &ValicoError, catalog: &MessageCatalog) -> String {
+    let template = match catalog.template(error) {
+        Some(template) => template,
+        None => return error.get_title().to_string()
+    };
+
+    let mut params = error.get_params();
+    params.insert("path".to_string(), Value::String(error.get_path().to_string()));
+    params.insert("detail".to_string(), Value::String(error.get_detail().unwrap_or("").to_string()));
+
+    // Error types of different modules share codes, like `required`
+    if placeholders(template).iter().any(|name| !params.contains_key(*name)) {
+        return error.get_title().to_string()
+    }
+
+    interpolate(template, &params)
+}
+
+/// The names of the `{name}` placeholders of the template.
+fn placeholders(template: &str) -> Vec<&str> {
+    let mut names = vec![];
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{") {
+        let end = match rest[start..].find("}") {
+            Some(end) => start + end,
+            None => break
+        };
+
+        names.push(&rest[start + 1..end]);
+        rest = &rest[end + 1..];
+    }
+
+    names
+}
+
+/// Replaces the `{name}` placeholders that have a parameter, leaving the others as is.
+pub fn interpolate(template: &str, params: &::serde_json::Map<String, Value>) -> String {
+    let mut rendered = String::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{") {
+        let end = match rest[start..].find("}") {
+            Some(end) => start + end,
+            None => break
+        };
+
+        rendered.push_str(&rest[..start]);
+        match params.get(&rest[start + 1..end]) {
+            Some(value) => rendered.push_str(&format_param(value)),
+            None => rendered.push_str(&rest[start..end + 1])
+        }
+        rest = &rest[end + 1..];
+    }
+
+    rendered.push_str(rest);
+    rendered
+}
+
+/// Renders a parameter in a message: strings without quotes, whole numbers
+/// without decimals and arrays as their items separated by commas.
+pub fn format_param(value: &Value) -> String {
+    match *value {
+        Value::String(ref value) => value.clone(),
+        Value::Array(ref items) => items.iter().map(format_param).collect::<Vec<String>>().join(", "),
+        // Whole floats, like the `10.0` of builders, read better as `10`
+        Value::Number(ref number) if number.as_f64().map(|n| n.fract() == 0.0 && n.abs() < 1e15).unwrap_or(false) => {
+            (number.as_f64().unwrap() as i64).to_string()
+        },
+        ref value => value.to_string()
+    }
+}
+
+#[test]
+fn interpolate_params() {
+    let mut params = ::serde_json::Map::new();
+    params.insert("limit".to_string(), Value::from(10.0));
+    params.insert("path".to_string(), Value::String("/a".to_string()));
+
+    assert_eq!(interpolate("{path} must be at most {limit}, not {actual}", &params), "/a must be at most 10, not {actual}");
+    assert_eq!(interpolate("unclosed {limit", &params), "unclosed {limit");
+}
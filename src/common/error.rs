@@ -7,12 +7,34 @@ use std::mem;
 use serde::{Serialize, Serializer};
 use serde_json::{Value, to_value};
 
-pub trait ValicoError : Error + Send + Debug + Typeable {
+pub trait ValicoError : Error + Send + Sync + Debug + Typeable {
     fn get_code(&self) -> &str;
     fn get_path(&self) -> &str;
     fn get_title(&self) -> &str;
     fn get_fragment(&self) -> &[String];
     fn get_detail(&self) -> Option<&str> { None }
+    /// The keys from the root schema to the keyword, following the references that
+    /// were taken, when it's known.
+    fn get_keyword_location(&self) -> Option<&[String]> { None }
+    /// The schema URI with the JSON pointer of the keyword, when it's known.
+    fn get_absolute_keyword_location(&self) -> Option<&str> { None }
+    /// Parameters that message catalogs can interpolate into their messages.
+    fn get_params(&self) -> ::serde_json::Map<String, Value> { ::serde_json::Map::new() }
+}
+
+/// JSON pointer of the keys, with `~` and `/` escaped.
+pub fn to_pointer(keys: &[String]) -> String {
+    keys.iter().map(|key| ["/", &escape_key(key)].concat()).collect()
+}
+
+/// Escapes `~` and `/` in a key to make it a segment of a JSON pointer.
+pub fn escape_key(key: &str) -> String {
+    key.replace("~", "~0").replace("/", "~1")
+}
+
+/// The key of an escaped JSON pointer segment.
+pub fn unescape_key(segment: &str) -> String {
+    segment.replace("~1", "/").replace("~0", "~")
 }
 
 impl ValicoError {
@@ -40,10 +62,28 @@ impl Serialize for ValicoError {
             Some(ref detail) => { map.insert("detail".to_string(), to_value(detail).unwrap()); },
             None => ()
         }
+        insert_context(self, &mut map);
         Value::Object(map).serialize(serializer)
     }
 }
 
+#[doc(hidden)]
+pub fn insert_context(error: &ValicoError, map: &mut ::serde_json::Map<String, Value>) {
+    let params = error.get_params();
+    if params.len() > 0 {
+        map.insert("params".to_string(), Value::Object(params));
+    }
+
+    match error.get_keyword_location() {
+        Some(location) => { map.insert("keyword_location".to_string(), to_value(to_pointer(location)).unwrap()); },
+        None => ()
+    }
+    match error.get_absolute_keyword_location() {
+        Some(location) => { map.insert("absolute_keyword_location".to_string(), to_value(location).unwrap()); },
+        None => ()
+    }
+}
+
 pub type ValicoErrors = Vec<Box<ValicoError>>;
 
 macro_rules! impl_basic_err {
@@ -63,7 +103,7 @@ macro_rules! impl_basic_err {
 }
 
 macro_rules! impl_err {
-    ($err:ty, $code:expr, $title:expr) => {
+    (@impl $err:ty, $code:expr, $title:expr, $($method:item)*) => {
         impl_basic_err!($err, $code);
 
         impl $crate::common::error::ValicoError for $err {
@@ -71,31 +111,66 @@ macro_rules! impl_err {
             fn get_title(&self) -> &str { $title }
             fn get_path(&self) -> &str { self.path.as_ref() }
             fn get_fragment(&self) -> &[String] { &self.fragment }
+            $($method)*
         }
     };
 
-    ($err:ty, $code:expr, $title:expr, +detail) => {
-        impl_basic_err!($err, $code);
+    ($err:ty, $code:expr, $title:expr) => {
+        impl_err!(@impl $err, $code, $title,);
+    };
 
-        impl $crate::common::error::ValicoError for $err {
-            fn get_code(&self) -> &str { $code }
-            fn get_title(&self) -> &str { $title }
-            fn get_path(&self) -> &str { self.path.as_ref() }
-            fn get_fragment(&self) -> &[String] { &self.fragment }
+    ($err:ty, $code:expr, $title:expr, +detail) => {
+        impl_err!(@impl $err, $code, $title,
             fn get_detail(&self) -> Option<&str> { Some(self.detail.as_ref()) }
-        }
+        );
     };
 
     ($err:ty, $code:expr, $title:expr, +opt_detail) => {
-        impl_basic_err!($err, $code);
-
-        impl $crate::common::error::ValicoError for $err {
-            fn get_code(&self) -> &str { $code }
-            fn get_title(&self) -> &str { $title }
-            fn get_path(&self) -> &str { self.path.as_ref() }
-            fn get_fragment(&self) -> &[String] { &self.fragment }
+        impl_err!(@impl $err, $code, $title,
             fn get_detail(&self) -> Option<&str> { self.detail.as_ref().map(|s| s.as_ref()) }
-        }
+        );
+    };
+
+    // Errors with a `location: KeywordLocation` field.
+    ($err:ty, $code:expr, $title:expr, +location) => {
+        impl_err!(@impl $err, $code, $title,
+            fn get_keyword_location(&self) -> Option<&[String]> { Some(&self.location.path) }
+            fn get_absolute_keyword_location(&self) -> Option<&str> { self.location.absolute.as_ref().map(|s| s.as_ref()) }
+        );
+    };
+
+    ($err:ty, $code:expr, $title:expr, +detail, +location) => {
+        impl_err!(@impl $err, $code, $title,
+            fn get_detail(&self) -> Option<&str> { Some(self.detail.as_ref()) }
+            fn get_keyword_location(&self) -> Option<&[String]> { Some(&self.location.path) }
+            fn get_absolute_keyword_location(&self) -> Option<&str> { self.location.absolute.as_ref().map(|s| s.as_ref()) }
+        );
+    };
+
+    // Errors with parameters in the listed fields.
+    ($err:ty, $code:expr, $title:expr, +location, +params($($param:ident),+)) => {
+        impl_err!(@impl $err, $code, $title,
+            fn get_keyword_location(&self) -> Option<&[String]> { Some(&self.location.path) }
+            fn get_absolute_keyword_location(&self) -> Option<&str> { self.location.absolute.as_ref().map(|s| s.as_ref()) }
+            fn get_params(&self) -> ::serde_json::Map<String, ::serde_json::Value> {
+                let mut params = ::serde_json::Map::new();
+                $(params.insert(stringify!($param).to_string(), ::serde_json::to_value(&self.$param).unwrap());)+
+                params
+            }
+        );
+    };
+
+    ($err:ty, $code:expr, $title:expr, +detail, +location, +params($($param:ident),+)) => {
+        impl_err!(@impl $err, $code, $title,
+            fn get_detail(&self) -> Option<&str> { Some(self.detail.as_ref()) }
+            fn get_keyword_location(&self) -> Option<&[String]> { Some(&self.location.path) }
+            fn get_absolute_keyword_location(&self) -> Option<&str> { self.location.absolute.as_ref().map(|s| s.as_ref()) }
+            fn get_params(&self) -> ::serde_json::Map<String, ::serde_json::Value> {
+                let mut params = ::serde_json::Map::new();
+                $(params.insert(stringify!($param).to_string(), ::serde_json::to_value(&self.$param).unwrap());)+
+                params
+            }
+        );
     }
 }
 
@@ -111,6 +186,7 @@ macro_rules! impl_serialize{
                     Some(ref detail) => { map.insert("detail".to_string(), to_value(detail).unwrap()); },
                     None => ()
                 }
+                $crate::common::error::insert_context(self, &mut map);
                 Value::Object(map).serialize(serializer)
             }
         }
@@ -127,6 +203,7 @@ macro_rules! impl_serialize{
                     Some(ref detail) => { map.insert("detail".to_string(), to_value(detail).unwrap()); },
                     None => ()
                 }
+                $crate::common::error::insert_context(self, &mut map);
                 $({
                     let closure = $sp;
                     closure(self, &mut map);
@@ -1 +1,2 @@
-#[macro_use] pub mod error;
\ No newline at end of file
+#[macro_use] pub mod error;
+pub mod catalog;
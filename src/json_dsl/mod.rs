@@ -4,6 +4,8 @@ mod param;
 pub mod errors;
 #[macro_use] pub mod validators;
 
+use std::collections;
+
 use super::json_schema;
 
 pub use self::param::Param;
@@ -60,7 +62,10 @@ impl<T> ExtendedResult<T> {
             value: value,
             state: json_schema::ValidationState {
                 errors: errors,
-                missing: vec![]
+                missing: vec![],
+                evaluated: collections::HashSet::new(),
+                annotations: vec![],
+                evaluations: vec![]
             }
         }
     }
@@ -167,6 +167,32 @@ impl Builder {
         self.obj_builder.set("additionalItems", Builder::build(build).into_json())
     }
 
+    pub fn contains<F>(&mut self, build: F) where F: FnOnce(&mut Builder) {
+        self.obj_builder.set("contains", Builder::build(build).into_json())
+    }
+
+    pub fn min_contains(&mut self, number: u64) {
+        self.obj_builder.set("minContains", number)
+    }
+
+    pub fn max_contains(&mut self, number: u64) {
+        self.obj_builder.set("maxContains", number)
+    }
+
+    pub fn prefix_items<F>(&mut self, build: F) where F: FnOnce(&mut SchemaArray) {
+        let mut items = SchemaArray::new();
+        build(&mut items);
+        self.obj_builder.set("prefixItems", items.items)
+    }
+
+    pub fn unevaluated_items(&mut self, allow: bool) {
+        self.obj_builder.set("unevaluatedItems", allow)
+    }
+
+    pub fn unevaluated_items_schema<F>(&mut self, build: F) where F: FnOnce(&mut Builder) {
+        self.obj_builder.set("unevaluatedItems", Builder::build(build).into_json())
+    }
+
     pub fn max_items(&mut self, number: u64) {
         self.obj_builder.set("maxItems", number)
     }
@@ -211,10 +237,26 @@ impl Builder {
         self.obj_builder.set("additionalProperties", Builder::build(build).into_json())
     }
 
+    pub fn unevaluated_properties(&mut self, allow: bool) {
+        self.obj_builder.set("unevaluatedProperties", allow)
+    }
+
+    pub fn unevaluated_properties_schema<F>(&mut self, build: F) where F: FnOnce(&mut Builder) {
+        self.obj_builder.set("unevaluatedProperties", Builder::build(build).into_json())
+    }
+
+    pub fn property_names<F>(&mut self, build: F) where F: FnOnce(&mut Builder) {
+        self.obj_builder.set("propertyNames", Builder::build(build).into_json())
+    }
+
     pub fn dependencies<F>(&mut self, build: F) where F: FnOnce(&mut Dependencies) {
         self.obj_builder.set("dependencies", Dependencies::build(build))
     }
 
+    pub fn const_<T>(&mut self, value: T) where T: Serialize {
+        self.obj_builder.set("const", value)
+    }
+
     pub fn enum_<F>(&mut self, build: F) where F: FnOnce(&mut jsonway::ArrayBuilder) {
         self.obj_builder.set("enum", jsonway::array(build).unwrap())
     }
@@ -269,6 +311,18 @@ impl Builder {
         self.obj_builder.set("not", Builder::build(build).into_json())
     }
 
+    pub fn if_<F>(&mut self, build: F) where F: FnOnce(&mut Builder) {
+        self.obj_builder.set("if", Builder::build(build).into_json())
+    }
+
+    pub fn then<F>(&mut self, build: F) where F: FnOnce(&mut Builder) {
+        self.obj_builder.set("then", Builder::build(build).into_json())
+    }
+
+    pub fn else_<F>(&mut self, build: F) where F: FnOnce(&mut Builder) {
+        self.obj_builder.set("else", Builder::build(build).into_json())
+    }
+
     pub fn build<F>(build: F) -> Builder where F: FnOnce(&mut Builder) {
         let mut builder = Builder::new();
         build(&mut builder);
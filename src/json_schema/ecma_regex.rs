@@ -0,0 +1,513 @@
+//! The ECMA-262 regular expressions of `pattern`, `patternProperties` and the
+//! `regex` format, translated to the syntax of the `regex` crate.
+//!
+//! Patterns are read with the semantics of the `u` flag, as JSON Schema requires.
+//! `\d`, `\w`, `\s`, `\b` and `.` keep their ECMA-262 meaning. Backreferences and
+//! lookarounds have no equivalent in the `regex` crate and are reported as
+//! unsupported.
+
+use std::char;
+use std::error;
+use std::fmt;
+use regex;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RegexError {
+    /// The pattern is valid ECMA-262 but uses a construct that can't be
+    /// translated, like a backreference.
+    Unsupported(&'static str),
+    /// The pattern isn't a valid regular expression.
+    Syntax(String),
+    /// The compiled pattern is bigger than the size limit, in bytes.
+    TooBig(usize)
+}
+
+impl fmt::Display for RegexError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RegexError::Unsupported(construct) => write!(fmt, "{} are not supported", construct),
+            RegexError::Syntax(ref detail) => write!(fmt, "{}", detail),
+            RegexError::TooBig(limit) => write!(fmt, "the compiled regular expression is bigger than the limit of {} bytes", limit)
+        }
+    }
+}
+
+impl error::Error for RegexError {}
+
+/// Translates and compiles the pattern.
+pub fn compile(pattern: &str) -> Result<regex::Regex, RegexError> {
+    compile_with_size_limit(pattern, None)
+}
+
+/// Translates and compiles the pattern, failing with `RegexError::TooBig` when the
+/// compiled program is bigger than `size_limit` bytes instead of the default limit
+/// of the `regex` crate.
+pub fn compile_with_size_limit(pattern: &str, size_limit: Option<usize>) -> Result<regex::Regex, RegexError> {
+    let translated = try!(translate(pattern));
+    let mut builder = regex::RegexBuilder::new(&translated);
+    if let Some(size_limit) = size_limit {
+        builder = builder.size_limit(size_limit);
+    }
+
+    builder.compile().map_err(|error| match error {
+        regex::Error::CompiledTooBig(limit) => RegexError::TooBig(limit),
+        error => RegexError::Syntax(error.to_string())
+    })
+}
+
+/// Rewrites the ECMA-262 pattern in the syntax of the `regex` crate.
+pub fn translate(pattern: &str) -> Result<String, RegexError> {
+    let mut translator = Translator {
+        chars: pattern.chars().collect(),
+        pos: 0,
+        out: String::new()
+    };
+
+    try!(translator.pattern());
+    Ok(translator.out)
+}
+
+type Ranges = &'static [(u32, u32)];
+
+const DIGIT: Ranges = &[(0x30, 0x39)];
+const WORD: Ranges = &[(0x30, 0x39), (0x41, 0x5A), (0x5F, 0x5F), (0x61, 0x7A)];
+/// WhiteSpace and LineTerminator of ECMA-262, sections 11.2 and 11.3.
+const SPACE: Ranges = &[
+    (0x9, 0xD), (0x20, 0x20), (0xA0, 0xA0), (0x1680, 0x1680), (0x2000, 0x200A),
+    (0x2028, 0x2029), (0x202F, 0x202F), (0x205F, 0x205F), (0x3000, 0x3000), (0xFEFF, 0xFEFF)
+];
+const LINE_TERMINATOR: Ranges = &[(0xA, 0xA), (0xD, 0xD), (0x2028, 0x2029)];
+
+/// The long names and aliases of the general categories, which the `regex`
+/// crate only knows by their short names.
+const GENERAL_CATEGORIES: [(&'static str, &'static str); 40] = [
+    ("Cased_Letter", "LC"), ("Close_Punctuation", "Pe"), ("Combining_Mark", "M"),
+    ("Connector_Punctuation", "Pc"), ("Control", "Cc"), ("Currency_Symbol", "Sc"),
+    ("Dash_Punctuation", "Pd"), ("Decimal_Number", "Nd"), ("Enclosing_Mark", "Me"),
+    ("Final_Punctuation", "Pf"), ("Format", "Cf"), ("Initial_Punctuation", "Pi"),
+    ("Letter", "L"), ("Letter_Number", "Nl"), ("Line_Separator", "Zl"),
+    ("Lowercase_Letter", "Ll"), ("Mark", "M"), ("Math_Symbol", "Sm"),
+    ("Modifier_Letter", "Lm"), ("Modifier_Symbol", "Sk"), ("Nonspacing_Mark", "Mn"),
+    ("Number", "N"), ("Open_Punctuation", "Ps"), ("Other", "C"),
+    ("Other_Letter", "Lo"), ("Other_Number", "No"), ("Other_Punctuation", "Po"),
+    ("Other_Symbol", "So"), ("Paragraph_Separator", "Zp"), ("Private_Use", "Co"),
+    ("Punctuation", "P"), ("Separator", "Z"), ("Space_Separator", "Zs"),
+    ("Spacing_Mark", "Mc"), ("Surrogate", "Cs"), ("Symbol", "S"),
+    ("Titlecase_Letter", "Lt"), ("Unassigned", "Cn"), ("Uppercase_Letter", "Lu"),
+    ("digit", "Nd")
+];
+
+/// An atom of the pattern that stands for characters.
+enum Atom {
+    Char(u32),
+    Ranges(Vec<(u32, u32)>),
+    /// A Unicode property, already in the syntax of the `regex` crate.
+    Property(String),
+    /// An assertion, which can't appear in a character class.
+    Assertion(&'static str)
+}
+
+fn complement(ranges: Ranges) -> Vec<(u32, u32)> {
+    let mut complement = vec![];
+    let mut next = 0;
+    for &(start, end) in ranges.iter() {
+        if start > next {
+            complement.push((next, start - 1));
+        }
+        next = end + 1;
+    }
+    if next <= 0x10FFFF {
+        complement.push((next, 0x10FFFF));
+    }
+
+    complement
+}
+
+/// A character of a class, escaped unless it is alphanumeric.
+fn class_char(code: u32) -> String {
+    match char::from_u32(code) {
+        Some(c) if c.is_ascii_alphanumeric() || !c.is_ascii() => c.to_string(),
+        _ => format!("\\x{{{:X}}}", code)
+    }
+}
+
+/// The ranges as the items of a class. Surrogates aren't characters, so they are
+/// left out.
+fn class_items(ranges: &[(u32, u32)]) -> String {
+    let mut items = String::new();
+    for &(start, end) in ranges.iter() {
+        let parts = if start < 0xD800 && end > 0xDFFF {
+            vec![(start, 0xD7FF), (0xE000, end)]
+        } else {
+            vec![(start, end)]
+        };
+
+        for &(start, end) in parts.iter() {
+            if start == end {
+                items.push_str(&class_char(start));
+            } else {
+                items.push_str(&format!("{}-{}", class_char(start), class_char(end)));
+            }
+        }
+    }
+
+    items
+}
+
+fn hex_value(digits: &[char]) -> Option<u32> {
+    let string: String = digits.iter().cloned().collect();
+    if string.len() > 0 && string.len() <= 6 && string.chars().all(|c| c.is_digit(16)) {
+        u32::from_str_radix(&string, 16).ok()
+    } else {
+        None
+    }
+}
+
+struct Translator {
+    chars: Vec<char>,
+    pos: usize,
+    out: String
+}
+
+impl Translator {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).cloned()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        if c.is_some() {
+            self.pos += 1;
+        }
+        c
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        if self.peek() == Some(expected) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn pattern(&mut self) -> Result<(), RegexError> {
+        while let Some(c) = self.next() {
+            match c {
+                '\\' => {
+                    let atom = try!(self.escape(false));
+                    let translated = match atom {
+                        Atom::Char(code) => class_char(code),
+                        Atom::Ranges(ranges) => format!("[{}]", class_items(&ranges)),
+                        Atom::Property(property) => property,
+                        Atom::Assertion(assertion) => assertion.to_string()
+                    };
+                    self.out.push_str(&translated);
+                },
+                '[' => try!(self.class()),
+                '(' => try!(self.group()),
+                '.' => {
+                    let items = class_items(&complement(LINE_TERMINATOR));
+                    self.out.push_str(&format!("[{}]", items));
+                },
+                '{' => try!(self.quantifier()),
+                '}' | ']' => return Err(RegexError::Syntax(format!("unescaped `{}`", c))),
+                '^' | '$' | '|' | '*' | '+' | '?' | ')' => self.out.push(c),
+                c => self.out.push_str(&class_char(c as u32))
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reads the escape after a `\`.
+    fn escape(&mut self, in_class: bool) -> Result<Atom, RegexError> {
+        let c = match self.next() {
+            Some(c) => c,
+            None => return Err(RegexError::Syntax("trailing backslash".to_string()))
+        };
+
+        Ok(match c {
+            'd' => Atom::Ranges(DIGIT.to_vec()),
+            'D' => Atom::Ranges(complement(DIGIT)),
+            'w' => Atom::Ranges(WORD.to_vec()),
+            'W' => Atom::Ranges(complement(WORD)),
+            's' => Atom::Ranges(SPACE.to_vec()),
+            'S' => Atom::Ranges(complement(SPACE)),
+            'b' if in_class => Atom::Char(0x8),
+            'b' => Atom::Assertion(r"(?-u:\b)"),
+            'B' if !in_class => Atom::Assertion(r"(?-u:\B)"),
+            't' => Atom::Char(0x9),
+            'n' => Atom::Char(0xA),
+            'v' => Atom::Char(0xB),
+            'f' => Atom::Char(0xC),
+            'r' => Atom::Char(0xD),
+            'c' => match self.next() {
+                Some(letter) if letter.is_ascii_alphabetic() => Atom::Char(letter as u32 % 32),
+                _ => return Err(RegexError::Syntax("`\\c` must be followed by a letter".to_string()))
+            },
+            '0' => match self.peek() {
+                Some(digit) if digit.is_ascii_digit() => return Err(RegexError::Syntax("octal escapes are not allowed".to_string())),
+                _ => Atom::Char(0)
+            },
+            '1' ..= '9' | 'k' => return Err(RegexError::Unsupported("backreferences")),
+            'x' => {
+                let digits = self.chars[self.pos..].iter().take(2).cloned().collect::<Vec<char>>();
+                match hex_value(&digits) {
+                    Some(code) if digits.len() == 2 => { self.pos += 2; Atom::Char(code) },
+                    _ => return Err(RegexError::Syntax("`\\x` must be followed by two hexadecimal digits".to_string()))
+                }
+            },
+            'u' => Atom::Char(try!(self.unicode_escape())),
+            'p' | 'P' => Atom::Property(try!(self.property(c == 'P'))),
+            '-' if in_class => Atom::Char('-' as u32),
+            '^' | '$' | '\\' | '.' | '*' | '+' | '?' | '(' | ')' | '[' | ']' | '{' | '}' | '|' | '/' => Atom::Char(c as u32),
+            c => return Err(RegexError::Syntax(format!("invalid escape `\\{}`", c)))
+        })
+    }
+
+    /// Reads `\uXXXX`, joining surrogate pairs, or `\u{X...}`.
+    fn unicode_escape(&mut self) -> Result<u32, RegexError> {
+        let invalid = || RegexError::Syntax("invalid unicode escape".to_string());
+
+        if self.eat('{') {
+            let digits = self.chars[self.pos..].iter().take_while(|&&c| c != '}').cloned().collect::<Vec<char>>();
+            self.pos += digits.len();
+            if !self.eat('}') { return Err(invalid()) }
+            return match hex_value(&digits) {
+                Some(code) if code <= 0x10FFFF && (code < 0xD800 || code > 0xDFFF) => Ok(code),
+                Some(code) if code <= 0x10FFFF => Err(RegexError::Unsupported("lone surrogates")),
+                _ => Err(invalid())
+            }
+        }
+
+        let high = try!(self.four_hex_digits().ok_or_else(&invalid));
+        if high < 0xD800 || high > 0xDFFF {
+            return Ok(high)
+        }
+
+        let start = self.pos;
+        if high < 0xDC00 && self.eat('\\') && self.eat('u') {
+            if let Some(low) = self.four_hex_digits() {
+                if low >= 0xDC00 && low <= 0xDFFF {
+                    return Ok(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00))
+                }
+            }
+        }
+        self.pos = start;
+
+        Err(RegexError::Unsupported("lone surrogates"))
+    }
+
+    fn four_hex_digits(&mut self) -> Option<u32> {
+        let digits = self.chars[self.pos..].iter().take(4).cloned().collect::<Vec<char>>();
+        if digits.len() < 4 { return None }
+        let value = hex_value(&digits);
+        if value.is_some() {
+            self.pos += 4;
+        }
+        value
+    }
+
+    /// Reads the `{...}` of `\p` or `\P`.
+    fn property(&mut self, negated: bool) -> Result<String, RegexError> {
+        let invalid = || RegexError::Syntax("`\\p` must be followed by a property in braces".to_string());
+
+        if !self.eat('{') { return Err(invalid()) }
+        let name = self.chars[self.pos..].iter().take_while(|&&c| c != '}').cloned().collect::<String>();
+        self.pos += name.chars().count();
+        if !self.eat('}') || name.len() == 0 { return Err(invalid()) }
+
+        let value = match name.find('=') {
+            Some(idx) => match &name[..idx] {
+                "General_Category" | "gc" | "Script" | "sc" | "Script_Extensions" | "scx" => &name[idx + 1..],
+                _ => return Err(RegexError::Syntax(format!("unknown property `{}`", &name[..idx])))
+            },
+            None => &name[..]
+        };
+        let value = GENERAL_CATEGORIES.iter()
+            .find(|&&(long, _)| long == value)
+            .map(|&(_, short)| short)
+            .unwrap_or(value);
+
+        Ok(format!("\\{}{{{}}}", if negated { "P" } else { "p" }, value))
+    }
+
+    /// Reads a class after its `[`.
+    fn class(&mut self) -> Result<(), RegexError> {
+        let negated = self.eat('^');
+        let mut items = String::new();
+
+        loop {
+            let start = match self.next() {
+                Some(']') => break,
+                Some(c) => try!(self.class_atom(c)),
+                None => return Err(RegexError::Syntax("unterminated character class".to_string()))
+            };
+
+            let is_range = self.peek() == Some('-') && match self.chars.get(self.pos + 1) {
+                Some(&']') | None => false,
+                Some(_) => true
+            };
+
+            if is_range {
+                self.pos += 1;
+                let c = self.next().unwrap();
+                let end = try!(self.class_atom(c));
+                match (start, end) {
+                    (Atom::Char(start), Atom::Char(end)) => {
+                        if start > end {
+                            return Err(RegexError::Syntax("range out of order in character class".to_string()))
+                        }
+                        items.push_str(&class_items(&[(start, end)]));
+                    },
+                    _ => return Err(RegexError::Syntax("character class escapes can't be bounds of a range".to_string()))
+                }
+            } else {
+                match start {
+                    Atom::Char(code) => items.push_str(&class_char(code)),
+                    Atom::Ranges(ranges) => items.push_str(&class_items(&ranges)),
+                    Atom::Property(property) => items.push_str(&property),
+                    Atom::Assertion(_) => unreachable!()
+                }
+            }
+        }
+
+        // `[]` matches nothing and `[^]` matches anything, but the `regex` crate
+        // doesn't allow empty classes.
+        if items.len() == 0 {
+            self.out.push_str(if negated { "(?s:.)" } else { r"(?:\b\B)" });
+        } else {
+            self.out.push_str(&format!("[{}{}]", if negated { "^" } else { "" }, items));
+        }
+
+        Ok(())
+    }
+
+    fn class_atom(&mut self, c: char) -> Result<Atom, RegexError> {
+        if c == '\\' {
+            self.escape(true)
+        } else {
+            Ok(Atom::Char(c as u32))
+        }
+    }
+
+    /// Reads a group after its `(`.
+    fn group(&mut self) -> Result<(), RegexError> {
+        if !self.eat('?') {
+            self.out.push('(');
+            return Ok(())
+        }
+
+        match self.next() {
+            Some(':') => self.out.push_str("(?:"),
+            Some('=') | Some('!') => return Err(RegexError::Unsupported("lookahead assertions")),
+            Some('<') if self.peek() == Some('=') || self.peek() == Some('!') => {
+                return Err(RegexError::Unsupported("lookbehind assertions"))
+            },
+            Some('<') => {
+                let name = self.chars[self.pos..].iter().take_while(|&&c| c != '>').cloned().collect::<String>();
+                self.pos += name.chars().count();
+                if !self.eat('>') || name.len() == 0 {
+                    return Err(RegexError::Syntax("invalid group name".to_string()))
+                }
+                self.out.push_str(&format!("(?P<{}>", name));
+            },
+            _ => return Err(RegexError::Syntax("invalid group".to_string()))
+        }
+
+        Ok(())
+    }
+
+    /// Reads a `{n}`, `{n,}` or `{n,m}` quantifier after its `{`.
+    fn quantifier(&mut self) -> Result<(), RegexError> {
+        let body = self.chars[self.pos..].iter().take_while(|&&c| c != '}').cloned().collect::<String>();
+        let is_quantifier = self.chars.get(self.pos + body.chars().count()).is_some() && {
+            let mut bounds = body.splitn(2, ',');
+            let min = bounds.next().unwrap();
+            let max = bounds.next().unwrap_or("0");
+            min.len() > 0 && min.chars().all(|c| c.is_ascii_digit()) && max.chars().all(|c| c.is_ascii_digit())
+        };
+
+        if !is_quantifier {
+            return Err(RegexError::Syntax("`{` must start a quantifier, escape it as `\\{`".to_string()))
+        }
+
+        self.pos += body.chars().count() + 1;
+        self.out.push_str(&format!("{{{}}}", body));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+fn is_match(pattern: &str, string: &str) -> bool {
+    compile(pattern).unwrap().is_match(string)
+}
+
+#[test]
+fn translate_classes() {
+    assert!(is_match(r"^\d+$", "42"));
+    assert!(!is_match(r"^\d$", "\u{07c0}"));
+    assert!(is_match(r"^\D$", "\u{07c0}"));
+    assert!(is_match(r"^\w$", "_"));
+    assert!(!is_match(r"^\w$", "é"));
+    assert!(is_match(r"^\W$", "é"));
+    assert!(is_match(r"^\s$", "\u{feff}"));
+    assert!(is_match(r"^\s$", "\u{2003}"));
+    assert!(!is_match(r"^\s$", "\u{85}"));
+    assert!(is_match(r"^\S$", "\u{85}"));
+    assert!(is_match(r"^[\d\s-]+$", "1 - 2"));
+    assert!(!is_match(r"^[^\W_]$", "_"));
+    assert!(is_match(r"^[\b]$", "\u{8}"));
+    assert!(is_match(r"^[a-]$", "-"));
+    assert!(!is_match(r"a[]", "a"));
+    assert!(is_match(r"^[^]$", "\n"));
+    assert!(!is_match(r"^.$", "\u{2028}"));
+    assert!(is_match(r"^.$", "🐲"));
+}
+
+#[test]
+fn translate_escapes() {
+    assert!(is_match(r"^\cC$", "\u{3}"));
+    assert!(is_match(r"^\cc$", "\u{3}"));
+    assert!(is_match(r"^\t\v\0$", "\t\u{b}\u{0}"));
+    assert!(is_match(r"^\x41\u0042\u{43}$", "ABC"));
+    assert!(is_match(r"^\uD83D\uDC32$", "🐲"));
+    assert!(is_match(r"^\/\.\{\}$", "/.{}"));
+    assert!(is_match(r"^a #&~$", "a #&~"));
+    assert!(is_match(r"\bcat\b", "a cat"));
+    // é isn't a word character in ECMA-262
+    assert!(is_match(r"\bcat\b", "écat"));
+    assert!(is_match(r"^\p{Letter}cole$", "école"));
+    assert!(is_match(r"^\p{digit}+$", "\u{09ea}\u{09e8}"));
+    assert!(is_match(r"^\p{Script=Greek}$", "α"));
+    assert!(is_match(r"^[\P{L}]$", "1"));
+}
+
+#[test]
+fn translate_groups_and_quantifiers() {
+    assert!(is_match(r"^(?<year>\d{4})-(?:\d{2}){1,2}$", "2020-12"));
+    assert_eq!(compile(r"^(?<year>\d{4})$").unwrap().captures("2020").unwrap().name("year"), Some("2020"));
+    assert!(is_match(r"^a{2,}?$", "aaa"));
+}
+
+#[test]
+fn rejects_unsupported_and_invalid() {
+    assert_eq!(translate(r"(a)\1"), Err(RegexError::Unsupported("backreferences")));
+    assert_eq!(translate(r"(?<a>.)\k<a>"), Err(RegexError::Unsupported("backreferences")));
+    assert_eq!(translate(r"a(?=b)"), Err(RegexError::Unsupported("lookahead assertions")));
+    assert_eq!(translate(r"a(?!b)"), Err(RegexError::Unsupported("lookahead assertions")));
+    assert_eq!(translate(r"(?<=a)b"), Err(RegexError::Unsupported("lookbehind assertions")));
+    assert_eq!(translate(r"\uD83D"), Err(RegexError::Unsupported("lone surrogates")));
+    assert_eq!(RegexError::Unsupported("backreferences").to_string(), "backreferences are not supported");
+
+    assert_eq!(compile_with_size_limit(r"\w{100}", Some(1000)).err(), Some(RegexError::TooBig(1000)));
+    assert!(compile_with_size_limit(r"\w{100}", None).is_ok());
+
+    for pattern in [r"\a", r"^(abc]", r"\c1", r"\x4", r"[z-a]", r"[\d-z]", r"[\B]", r"a{", r"}", r"\pL", r"\", r"(?i)a"].iter() {
+        match compile(pattern) {
+            Err(RegexError::Syntax(_)) => (),
+            other => panic!("{} gave {:?}", pattern, other.map(|_| ()))
+        }
+    }
+}
@@ -0,0 +1,262 @@
+//! The `errorMessage` keyword of ajv-errors, which replaces the errors of a schema
+//! with custom messages.
+//!
+//! The messages can refer to the instance with `${0}` (or to a part of it with a
+//! JSON pointer, like `${0/name}`) and to the value of the failed keyword with
+//! `${limit}`.
+
+use serde_json::{Value};
+use std::collections;
+
+use super::super::common::catalog;
+use super::super::common::error::{self, ValicoErrors, to_pointer};
+use super::errors;
+use super::schema;
+use super::validators;
+
+#[derive(Debug)]
+pub enum ErrorMessage {
+    /// Replaces every error of the schema with one message.
+    All(String),
+    /// Replaces the errors of each keyword with its message, `properties` and
+    /// `required` messages are keyed by property name and `_` is used for the rest.
+    Keywords {
+        keywords: collections::BTreeMap<String, String>,
+        properties: collections::BTreeMap<String, String>,
+        required: collections::BTreeMap<String, String>,
+        default: Option<String>
+    }
+}
+
+fn malformed(ctx: &schema::WalkContext, detail: &str) -> schema::SchemaError {
+    schema::SchemaError::Malformed {
+        url: (**ctx.document).clone(),
+        pointer: to_pointer(&ctx.keyword_fragment("errorMessage")),
+        detail: detail.to_string()
+    }
+}
+
+fn compile_messages(def: &Value, ctx: &schema::WalkContext, key: &str) -> Result<collections::BTreeMap<String, String>, schema::SchemaError> {
+    let mut messages = collections::BTreeMap::new();
+
+    for (property, message) in def.as_object().unwrap().iter() {
+        match message.as_str() {
+            Some(message) => { messages.insert(property.clone(), message.to_string()); },
+            None => return Err(malformed(ctx, &format!("The messages of `{}` must be strings", key)))
+        }
+    }
+
+    Ok(messages)
+}
+
+/// Compiles the `errorMessage` of the schema, if it has one.
+pub fn compile(def: &Value, ctx: &schema::WalkContext) -> Result<Option<ErrorMessage>, schema::SchemaError> {
+    let error_message = match def.get("errorMessage") {
+        Some(error_message) => error_message,
+        None => return Ok(None)
+    };
+
+    if error_message.is_string() {
+        return Ok(Some(ErrorMessage::All(error_message.as_str().unwrap().to_string())))
+    }
+
+    if !error_message.is_object() {
+        return Err(malformed(ctx, "The value of errorMessage MUST be a string or an object"))
+    }
+
+    let mut keywords = collections::BTreeMap::new();
+    let mut properties = collections::BTreeMap::new();
+    let mut required = collections::BTreeMap::new();
+    let mut default = None;
+
+    for (key, message) in error_message.as_object().unwrap().iter() {
+        match (key.as_ref(), message) {
+            ("properties", &Value::Object(_)) => properties = try!(compile_messages(message, ctx, key)),
+            ("required", &Value::Object(_)) => required = try!(compile_messages(message, ctx, key)),
+            ("_", &Value::String(ref message)) => default = Some(message.clone()),
+            (_, &Value::String(ref message)) => { keywords.insert(key.clone(), message.clone()); },
+            _ => return Err(malformed(ctx, &format!("The message of `{}` must be a string", key)))
+        }
+    }
+
+    Ok(Some(ErrorMessage::Keywords {
+        keywords: keywords,
+        properties: properties,
+        required: required,
+        default: default
+    }))
+}
+
+/// The errors replaced by one message.
+struct Group<'a> {
+    message: &'a str,
+    path: String,
+    errors: ValicoErrors
+}
+
+impl ErrorMessage {
+    /// Replaces the errors that the schema at `fragment` found in the instance `data`
+    /// at `path`.
+    pub fn apply(&self, state: &mut validators::ValidationState, data: &Value, path: &str, fragment: &[String], dynamic_scope: &validators::DynamicScope) {
+        let prefix_len = dynamic_scope.keyword_location(fragment).len();
+        let keyword_of = |error: &Box<super::super::common::error::ValicoError>| {
+            error.get_keyword_location().and_then(|location| location.get(prefix_len)).cloned()
+        };
+
+        let mut kept = vec![];
+        let mut groups: Vec<Group> = vec![];
+
+        for error in state.errors.drain(..) {
+            let keyword = keyword_of(&error);
+
+            let group = match *self {
+                ErrorMessage::All(ref message) => Some((message.as_ref(), path.to_string())),
+                ErrorMessage::Keywords { ref keywords, ref properties, ref required, ref default } => {
+                    let property = error.get_path().get(path.len() + 1..).map(|rest| error::unescape_key(rest.split("/").next().unwrap()));
+                    let by_property = match keyword.as_ref().map(|keyword| keyword.as_ref()) {
+                        Some("properties") => property.as_ref().and_then(|property| properties.get(property).map(|message| (message, property))),
+                        Some("required") => property.as_ref().and_then(|property| required.get(property).map(|message| (message, property))),
+                        _ => None
+                    };
+
+                    if let Some((message, property)) = by_property {
+                        Some((message.as_ref(), validators::property_path(path, property)))
+                    } else if let Some(message) = keyword.as_ref().and_then(|keyword| keywords.get(keyword)) {
+                        Some((message.as_ref(), path.to_string()))
+                    } else {
+                        default.as_ref().map(|message| (message.as_ref(), path.to_string()))
+                    }
+                }
+            };
+
+            match group {
+                Some((message, group_path)) => {
+                    match groups.iter().position(|group| group.message == message && group.path == group_path) {
+                        Some(idx) => groups[idx].errors.push(error),
+                        None => groups.push(Group {
+                            message: message,
+                            path: group_path,
+                            errors: vec![error]
+                        })
+                    }
+                },
+                None => kept.push(error)
+            }
+        }
+
+        for group in groups.into_iter() {
+            // The limit of the failed keyword, like the `18` of `minimum: 18`
+            let limit = group.errors.iter().filter_map(|error| error.get_params().remove("limit")).next();
+
+            kept.push(Box::new(errors::ErrorMessage {
+                path: group.path,
+                fragment: fragment.to_vec(),
+                location: dynamic_scope.locate_keyword(fragment, "errorMessage"),
+                detail: render(group.message, data, limit.as_ref()),
+                errors: group.errors
+            }));
+        }
+
+        state.errors = kept;
+    }
+}
+
+/// Replaces the `${0...}` and `${limit}` placeholders of the message.
+fn render(message: &str, data: &Value, limit: Option<&Value>) -> String {
+    let mut rendered = String::new();
+    let mut rest = message;
+
+    while let Some(start) = rest.find("${") {
+        let end = match rest[start..].find("}") {
+            Some(end) => start + end,
+            None => break
+        };
+
+        let placeholder = &rest[start + 2..end];
+        let value = if placeholder == "limit" {
+            limit
+        } else if placeholder.starts_with("0") {
+            data.pointer(&placeholder[1..])
+        } else {
+            None
+        };
+
+        rendered.push_str(&rest[..start]);
+        match value {
+            Some(value) => rendered.push_str(&catalog::format_param(value)),
+            None => rendered.push_str(&rest[start..end + 1])
+        }
+        rest = &rest[end + 1..];
+    }
+
+    rendered.push_str(rest);
+    rendered
+}
+
+#[cfg(test)] use super::scope;
+#[cfg(test)] use jsonway;
+#[cfg(test)] use serde_json::to_value;
+
+#[test]
+fn replaces_every_error() {
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(jsonway::object(|schema| {
+        schema.set("type", "string".to_string());
+        schema.set("minLength", 3);
+        schema.set("errorMessage", "`${0}` is not a valid name".to_string());
+    }).unwrap(), true).ok().unwrap();
+
+    let state = schema.validate(&to_value(5).unwrap());
+    assert_eq!(state.errors.len(), 1);
+    assert_eq!(state.errors[0].get_code(), "error_message");
+    assert_eq!(state.errors[0].get_title(), "`5` is not a valid name");
+    assert_eq!(state.errors[0].get_keyword_location().unwrap(), &["errorMessage".to_string()]);
+    assert!(schema.validate(&to_value("abc").unwrap()).is_valid());
+}
+
+#[test]
+fn replaces_keyword_errors() {
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(jsonway::object(|schema| {
+        schema.object("properties", |props| {
+            props.object("age", |age| age.set("minimum", 18));
+            props.object("name", |name| name.set("pattern", "^[a-z]+$".to_string()));
+        });
+        schema.array("required", |required| {
+            required.push("name".to_string());
+            required.push("email".to_string());
+        });
+        schema.set("maxProperties", 1);
+        schema.object("errorMessage", |messages| {
+            messages.object("properties", |props| props.set("age", "Must be at least ${limit}, not ${0/age}".to_string()));
+            messages.object("required", |required| required.set("email", "Email is required".to_string()));
+            messages.set("maxProperties", "At most ${limit} properties".to_string());
+        });
+    }).unwrap(), true).ok().unwrap();
+
+    let state = schema.validate(&jsonway::object(|obj| {
+        obj.set("age", 12);
+        obj.set("name", "Bob".to_string());
+    }).unwrap());
+
+    let mut errors = state.errors.iter().map(|error| {
+        (error.get_path().to_string(), error.get_detail().unwrap_or(error.get_title()).to_string())
+    }).collect::<Vec<(String, String)>>();
+    errors.sort();
+
+    assert_eq!(errors, vec![
+        ("".to_string(), "At most 1 properties".to_string()),
+        ("/age".to_string(), "Must be at least 18, not 12".to_string()),
+        ("/email".to_string(), "Email is required".to_string()),
+        ("/name".to_string(), "Pattern condition is not met".to_string())
+    ]);
+}
+
+#[test]
+fn rejects_malformed_messages() {
+    let mut scope = scope::Scope::new();
+    assert!(scope.compile(jsonway::object(|schema| schema.set("errorMessage", 1)).unwrap(), true).is_err());
+    assert!(scope.compile(jsonway::object(|schema| {
+        schema.object("errorMessage", |messages| messages.set("type", 1));
+    }).unwrap(), true).is_err());
+}
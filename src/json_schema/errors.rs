@@ -1,16 +1,29 @@
 use std::error::{Error};
 use super::super::common::error::ValicoError;
-use serde_json::{Value, to_value};
+use serde_json::{Number, Value, to_value};
 use serde::{Serialize, Serializer};
 
+/// Where the keyword that produced an error is.
+#[derive(Debug, Clone, Default)]
+pub struct KeywordLocation {
+    /// Keys from the schema the validation started at to the keyword, including the
+    /// `$ref` keywords that were followed on the way.
+    pub path: Vec<String>,
+    /// URI of the schema document with the JSON pointer of the keyword as the fragment.
+    pub absolute: Option<String>
+}
+
 #[derive(Debug)]
 #[allow(missing_copy_implementations)]
 pub struct WrongType {
     pub path: String,
     pub fragment: Vec<String>,
-    pub detail: String
+    pub location: KeywordLocation,
+    pub detail: String,
+    pub expected: Vec<String>,
+    pub actual: String
 }
-impl_err!(WrongType, "wrong_type", "Type of the value is wrong", +detail);
+impl_err!(WrongType, "wrong_type", "Type of the value is wrong", +detail, +location, +params(expected, actual));
 impl_serialize!(WrongType);
 
 #[derive(Debug)]
@@ -18,8 +31,11 @@ impl_serialize!(WrongType);
 pub struct MultipleOf {
     pub path: String,
     pub fragment: Vec<String>,
+    pub location: KeywordLocation,
+    pub multiple_of: Number,
+    pub actual: Number
 }
-impl_err!(MultipleOf, "multiple_of", "Wrong number of the value");
+impl_err!(MultipleOf, "multiple_of", "Wrong number of the value", +location, +params(multiple_of, actual));
 impl_serialize!(MultipleOf);
 
 #[derive(Debug)]
@@ -27,8 +43,12 @@ impl_serialize!(MultipleOf);
 pub struct Maximum {
     pub path: String,
     pub fragment: Vec<String>,
+    pub location: KeywordLocation,
+    pub limit: Number,
+    pub exclusive: bool,
+    pub actual: Number
 }
-impl_err!(Maximum, "maximum", "Maximum condition is not met");
+impl_err!(Maximum, "maximum", "Maximum condition is not met", +location, +params(limit, exclusive, actual));
 impl_serialize!(Maximum);
 
 #[derive(Debug)]
@@ -36,8 +56,12 @@ impl_serialize!(Maximum);
 pub struct Minimum {
     pub path: String,
     pub fragment: Vec<String>,
+    pub location: KeywordLocation,
+    pub limit: Number,
+    pub exclusive: bool,
+    pub actual: Number
 }
-impl_err!(Minimum, "minimum", "Minimum condition is not met");
+impl_err!(Minimum, "minimum", "Minimum condition is not met", +location, +params(limit, exclusive, actual));
 impl_serialize!(Minimum);
 
 #[derive(Debug)]
@@ -45,8 +69,11 @@ impl_serialize!(Minimum);
 pub struct MaxLength {
     pub path: String,
     pub fragment: Vec<String>,
+    pub location: KeywordLocation,
+    pub limit: u64,
+    pub actual: u64
 }
-impl_err!(MaxLength, "max_length", "MaxLength condition is not met");
+impl_err!(MaxLength, "max_length", "MaxLength condition is not met", +location, +params(limit, actual));
 impl_serialize!(MaxLength);
 
 #[derive(Debug)]
@@ -54,8 +81,11 @@ impl_serialize!(MaxLength);
 pub struct MinLength {
     pub path: String,
     pub fragment: Vec<String>,
+    pub location: KeywordLocation,
+    pub limit: u64,
+    pub actual: u64
 }
-impl_err!(MinLength, "min_length", "MinLength condition is not met");
+impl_err!(MinLength, "min_length", "MinLength condition is not met", +location, +params(limit, actual));
 impl_serialize!(MinLength);
 
 #[derive(Debug)]
@@ -63,8 +93,10 @@ impl_serialize!(MinLength);
 pub struct Pattern {
     pub path: String,
     pub fragment: Vec<String>,
+    pub location: KeywordLocation,
+    pub pattern: String
 }
-impl_err!(Pattern, "pattern", "Pattern condition is not met");
+impl_err!(Pattern, "pattern", "Pattern condition is not met", +location, +params(pattern));
 impl_serialize!(Pattern);
 
 #[derive(Debug)]
@@ -72,8 +104,11 @@ impl_serialize!(Pattern);
 pub struct MaxItems {
     pub path: String,
     pub fragment: Vec<String>,
+    pub location: KeywordLocation,
+    pub limit: u64,
+    pub actual: u64
 }
-impl_err!(MaxItems, "max_items", "MaxItems condition is not met");
+impl_err!(MaxItems, "max_items", "MaxItems condition is not met", +location, +params(limit, actual));
 impl_serialize!(MaxItems);
 
 #[derive(Debug)]
@@ -81,8 +116,11 @@ impl_serialize!(MaxItems);
 pub struct MinItems {
     pub path: String,
     pub fragment: Vec<String>,
+    pub location: KeywordLocation,
+    pub limit: u64,
+    pub actual: u64
 }
-impl_err!(MinItems, "min_items", "MinItems condition is not met");
+impl_err!(MinItems, "min_items", "MinItems condition is not met", +location, +params(limit, actual));
 impl_serialize!(MinItems);
 
 #[derive(Debug)]
@@ -90,8 +128,9 @@ impl_serialize!(MinItems);
 pub struct UniqueItems {
     pub path: String,
     pub fragment: Vec<String>,
+    pub location: KeywordLocation,
 }
-impl_err!(UniqueItems, "unique_items", "UniqueItems condition is not met");
+impl_err!(UniqueItems, "unique_items", "UniqueItems condition is not met", +location);
 impl_serialize!(UniqueItems);
 
 #[derive(Debug)]
@@ -99,9 +138,10 @@ impl_serialize!(UniqueItems);
 pub struct Items {
     pub path: String,
     pub fragment: Vec<String>,
+    pub location: KeywordLocation,
     pub detail: String
 }
-impl_err!(Items, "items", "Items condition is not met", +detail);
+impl_err!(Items, "items", "Items condition is not met", +detail, +location);
 impl_serialize!(Items);
 
 #[derive(Debug)]
@@ -109,8 +149,11 @@ impl_serialize!(Items);
 pub struct MaxProperties {
     pub path: String,
     pub fragment: Vec<String>,
+    pub location: KeywordLocation,
+    pub limit: u64,
+    pub actual: u64
 }
-impl_err!(MaxProperties, "max_properties", "MaxProperties condition is not met");
+impl_err!(MaxProperties, "max_properties", "MaxProperties condition is not met", +location, +params(limit, actual));
 impl_serialize!(MaxProperties);
 
 #[derive(Debug)]
@@ -118,8 +161,11 @@ impl_serialize!(MaxProperties);
 pub struct MinProperties {
     pub path: String,
     pub fragment: Vec<String>,
+    pub location: KeywordLocation,
+    pub limit: u64,
+    pub actual: u64
 }
-impl_err!(MinProperties, "min_properties", "MinProperties condition is not met");
+impl_err!(MinProperties, "min_properties", "MinProperties condition is not met", +location, +params(limit, actual));
 impl_serialize!(MinProperties);
 
 #[derive(Debug)]
@@ -127,8 +173,10 @@ impl_serialize!(MinProperties);
 pub struct Required {
     pub path: String,
     pub fragment: Vec<String>,
+    pub location: KeywordLocation,
+    pub property: String
 }
-impl_err!(Required, "required", "This property is required");
+impl_err!(Required, "required", "This property is required", +location, +params(property));
 impl_serialize!(Required);
 
 #[derive(Debug)]
@@ -136,9 +184,10 @@ impl_serialize!(Required);
 pub struct Properties {
     pub path: String,
     pub fragment: Vec<String>,
+    pub location: KeywordLocation,
     pub detail: String,
 }
-impl_err!(Properties, "properties", "Property conditions are not met", +detail);
+impl_err!(Properties, "properties", "Property conditions are not met", +detail, +location);
 impl_serialize!(Properties);
 
 #[derive(Debug)]
@@ -146,8 +195,10 @@ impl_serialize!(Properties);
 pub struct Enum {
     pub path: String,
     pub fragment: Vec<String>,
+    pub location: KeywordLocation,
+    pub allowed: Vec<Value>
 }
-impl_err!(Enum, "enum", "Enum conditions are not met");
+impl_err!(Enum, "enum", "Enum conditions are not met", +location, +params(allowed));
 impl_serialize!(Enum);
 
 #[derive(Debug)]
@@ -155,9 +206,10 @@ impl_serialize!(Enum);
 pub struct AnyOf {
     pub path: String,
     pub fragment: Vec<String>,
+    pub location: KeywordLocation,
     pub states: Vec<super::validators::ValidationState>
 }
-impl_err!(AnyOf, "any_of", "AnyOf conditions are not met");
+impl_err!(AnyOf, "any_of", "AnyOf conditions are not met", +location);
 impl_serialize!(AnyOf, |err: &AnyOf, map: &mut ::serde_json::Map<String, Value>| {
     map.insert("states".to_string(), to_value(&err.states).unwrap())
 });
@@ -167,9 +219,10 @@ impl_serialize!(AnyOf, |err: &AnyOf, map: &mut ::serde_json::Map<String, Value>|
 pub struct OneOf {
     pub path: String,
     pub fragment: Vec<String>,
+    pub location: KeywordLocation,
     pub states: Vec<super::validators::ValidationState>
 }
-impl_err!(OneOf, "one_of", "OneOf conditions are not met");
+impl_err!(OneOf, "one_of", "OneOf conditions are not met", +location);
 impl_serialize!(OneOf, |err: &OneOf, map: &mut ::serde_json::Map<String, Value>| {
     map.insert("states".to_string(), to_value(&err.states).unwrap())
 });
@@ -179,8 +232,9 @@ impl_serialize!(OneOf, |err: &OneOf, map: &mut ::serde_json::Map<String, Value>|
 pub struct Not {
     pub path: String,
     pub fragment: Vec<String>,
+    pub location: KeywordLocation,
 }
-impl_err!(Not, "not", "Not condition is not met");
+impl_err!(Not, "not", "Not condition is not met", +location);
 impl_serialize!(Not);
 
 #[derive(Debug)]
@@ -188,8 +242,140 @@ impl_serialize!(Not);
 pub struct Format {
     pub path: String,
     pub fragment: Vec<String>,
-    pub detail: String
+    pub location: KeywordLocation,
+    pub detail: String,
+    pub format: String
 }
-impl_err!(Format, "format", "Format is wrong", +detail);
+impl_err!(Format, "format", "Format is wrong", +detail, +location, +params(format));
 impl_serialize!(Format);
 
+
+#[derive(Debug)]
+#[allow(missing_copy_implementations)]
+pub struct Const {
+    pub path: String,
+    pub fragment: Vec<String>,
+    pub location: KeywordLocation,
+    pub expected: Value
+}
+impl_err!(Const, "const", "Const condition is not met", +location, +params(expected));
+impl_serialize!(Const);
+
+#[derive(Debug)]
+#[allow(missing_copy_implementations)]
+pub struct Contains {
+    pub path: String,
+    pub fragment: Vec<String>,
+    pub location: KeywordLocation,
+}
+impl_err!(Contains, "contains", "Contains condition is not met", +location);
+impl_serialize!(Contains);
+
+#[derive(Debug)]
+#[allow(missing_copy_implementations)]
+pub struct MinContains {
+    pub path: String,
+    pub fragment: Vec<String>,
+    pub location: KeywordLocation,
+    pub limit: u64,
+    pub actual: u64
+}
+impl_err!(MinContains, "min_contains", "MinContains condition is not met", +location, +params(limit, actual));
+impl_serialize!(MinContains);
+
+#[derive(Debug)]
+#[allow(missing_copy_implementations)]
+pub struct MaxContains {
+    pub path: String,
+    pub fragment: Vec<String>,
+    pub location: KeywordLocation,
+    pub limit: u64,
+    pub actual: u64
+}
+impl_err!(MaxContains, "max_contains", "MaxContains condition is not met", +location, +params(limit, actual));
+impl_serialize!(MaxContains);
+
+#[derive(Debug)]
+#[allow(missing_copy_implementations)]
+pub struct PropertyNames {
+    pub path: String,
+    pub fragment: Vec<String>,
+    pub location: KeywordLocation,
+    pub detail: String,
+    pub states: Vec<super::validators::ValidationState>
+}
+impl_err!(PropertyNames, "property_names", "PropertyNames condition is not met", +detail, +location);
+impl_serialize!(PropertyNames, |err: &PropertyNames, map: &mut ::serde_json::Map<String, Value>| {
+    map.insert("states".to_string(), to_value(&err.states).unwrap())
+});
+
+#[derive(Debug)]
+#[allow(missing_copy_implementations)]
+pub struct BooleanSchema {
+    pub path: String,
+    pub fragment: Vec<String>,
+    pub location: KeywordLocation,
+}
+impl_err!(BooleanSchema, "boolean_schema", "False schema does not allow any value", +location);
+impl_serialize!(BooleanSchema);
+
+#[derive(Debug)]
+#[allow(missing_copy_implementations)]
+pub struct ReferenceDepth {
+    pub path: String,
+    pub fragment: Vec<String>,
+    pub location: KeywordLocation,
+    pub limit: u64
+}
+impl_err!(ReferenceDepth, "reference_depth", "Too many nested references", +location, +params(limit));
+impl_serialize!(ReferenceDepth);
+
+/// The states of the subschemas that failed under a combinator error.
+pub fn nested_states(error: &ValicoError) -> &[super::validators::ValidationState] {
+    if let Some(error) = error.downcast::<AnyOf>() {
+        &error.states
+    } else if let Some(error) = error.downcast::<OneOf>() {
+        &error.states
+    } else if let Some(error) = error.downcast::<PropertyNames>() {
+        &error.states
+    } else {
+        &[]
+    }
+}
+
+/// Error of a validation that only checks whether the instance is valid, standing
+/// for the error that wasn't built.
+#[derive(Debug)]
+pub struct Invalid;
+impl_basic_err!(Invalid, "invalid");
+impl ValicoError for Invalid {
+    fn get_code(&self) -> &str { "invalid" }
+    fn get_title(&self) -> &str { "The value is not valid" }
+    fn get_path(&self) -> &str { "" }
+    fn get_fragment(&self) -> &[String] { &[] }
+}
+impl_serialize!(Invalid);
+
+#[derive(Debug)]
+#[allow(missing_copy_implementations)]
+pub struct ErrorMessage {
+    pub path: String,
+    pub fragment: Vec<String>,
+    pub location: KeywordLocation,
+    pub detail: String,
+    /// The errors replaced by the message.
+    pub errors: super::super::common::error::ValicoErrors
+}
+impl_basic_err!(ErrorMessage, "error_message");
+impl ValicoError for ErrorMessage {
+    fn get_code(&self) -> &str { "error_message" }
+    fn get_title(&self) -> &str { self.detail.as_ref() }
+    fn get_path(&self) -> &str { self.path.as_ref() }
+    fn get_fragment(&self) -> &[String] { &self.fragment }
+    fn get_detail(&self) -> Option<&str> { Some(self.detail.as_ref()) }
+    fn get_keyword_location(&self) -> Option<&[String]> { Some(&self.location.path) }
+    fn get_absolute_keyword_location(&self) -> Option<&str> { self.location.absolute.as_ref().map(|s| s.as_ref()) }
+}
+impl_serialize!(ErrorMessage, |err: &ErrorMessage, map: &mut ::serde_json::Map<String, Value>| {
+    map.insert("errors".to_string(), to_value(&err.errors).unwrap())
+});
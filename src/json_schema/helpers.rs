@@ -1,10 +1,8 @@
 use serde_json::{Value};
-use url::{Url};
+use url::{self, Url};
 use url::percent_encoding;
 use uuid::{Uuid};
 
-use super::schema;
-
 pub fn generate_id() -> Url {
     let uuid = Uuid::new_v4();
     Url::parse(&format!("json-schema://{}", uuid)).unwrap()
@@ -27,13 +25,37 @@ pub fn connect(strings: &[&str]) -> String {
     strings.iter().map(|s| encode(s)).collect::<Vec<String>>().join("/")
 }
 
-pub fn parse_url_key(key: &str, obj: &Value) -> Result<Option<Url>, schema::SchemaError> {
+/// JSON Schema equality, where numbers are equal when their values are, so `1.0`
+/// equals `1`, also inside arrays and objects.
+pub fn is_equal(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (&Value::Number(ref left), &Value::Number(ref right)) => {
+            if let (Some(left), Some(right)) = (left.as_u64(), right.as_u64()) {
+                left == right
+            } else if let (Some(left), Some(right)) = (left.as_i64(), right.as_i64()) {
+                left == right
+            } else {
+                left.as_f64() == right.as_f64()
+            }
+        },
+        (&Value::Array(ref left), &Value::Array(ref right)) => {
+            left.len() == right.len() &&
+                left.iter().zip(right.iter()).all(|(left, right)| is_equal(left, right))
+        },
+        (&Value::Object(ref left), &Value::Object(ref right)) => {
+            left.len() == right.len() &&
+                left.iter().all(|(key, left)| right.get(key).map(|right| is_equal(left, right)).unwrap_or(false))
+        },
+        _ => left == right
+    }
+}
+
+pub fn parse_url_key(key: &str, obj: &Value) -> Result<Option<Url>, url::ParseError> {
     match obj.get(key) {
         Some(value) => {
             match value.as_str() {
                 Some(string) => Url::parse(string)
-                                .map(|url| Some(url))
-                                .map_err(|err| schema::SchemaError::UrlParseError(err)),
+                                .map(|url| Some(url)),
                 None => Ok(None)
             }
         },
@@ -41,15 +63,14 @@ pub fn parse_url_key(key: &str, obj: &Value) -> Result<Option<Url>, schema::Sche
     }
 }
 
-pub fn parse_url_key_with_base(key: &str, obj: &Value, base: &Url) -> Result<Option<Url>, schema::SchemaError> {
+pub fn parse_url_key_with_base(key: &str, obj: &Value, base: &Url) -> Result<Option<Url>, url::ParseError> {
     match obj.get(key) {
         Some(value) => {
             match value.as_str() {
                 Some(string) => Url::options()
                                 .base_url(Some(base))
                                 .parse(string)
-                                .map(|url| Some(url))
-                                .map_err(|err| schema::SchemaError::UrlParseError(err)),
+                                .map(|url| Some(url)),
                 None => Ok(None)
             }
         },
@@ -0,0 +1,76 @@
+use serde_json::{Value};
+
+use super::super::schema;
+use super::super::validators;
+
+#[allow(missing_copy_implementations)]
+pub struct Const;
+impl super::Keyword for Const {
+    fn compile(&self, def: &Value, ctx: &schema::WalkContext) -> super::KeywordResult {
+        let const_ = keyword_key_exists!(def, "const");
+
+        Ok(Some(Box::new(validators::Const {
+            fragment: ctx.fragment.clone(),
+            keyword: "const",
+            item: const_.clone()
+        })))
+    }
+}
+
+#[cfg(test)] use super::super::scope;
+#[cfg(test)] use jsonway;
+#[cfg(test)] use super::super::builder;
+#[cfg(test)] use serde_json::to_value;
+
+#[test]
+fn validate() {
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(builder::schema(|s| {
+        s.schema("http://json-schema.org/draft-06/schema#");
+        s.const_(jsonway::object(|obj| {
+            obj.set("a", 1);
+        }).unwrap());
+    }).into_json(), true).ok().unwrap();
+
+    assert_eq!(schema.validate(&jsonway::object(|obj| {
+        obj.set("a", 1);
+    }).unwrap()).is_valid(), true);
+
+    assert_eq!(schema.validate(&jsonway::object(|obj| {
+        obj.set("a", 2);
+    }).unwrap()).is_valid(), false);
+
+    assert_eq!(schema.validate(&to_value(&"a").unwrap()).is_valid(), false);
+}
+
+#[test]
+fn ignored_in_draft4() {
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(builder::schema(|s| {
+        s.const_(1);
+    }).into_json(), false).ok().unwrap();
+
+    assert_eq!(schema.validate(&to_value(&2).unwrap()).is_valid(), true);
+}
+
+#[test]
+fn validate_numbers_by_value() {
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(builder::schema(|s| {
+        s.schema("http://json-schema.org/draft-06/schema#");
+        s.const_(jsonway::object(|obj| {
+            obj.set("a", 1);
+            obj.array("b", |arr| { arr.push(2); });
+        }).unwrap());
+    }).into_json(), true).ok().unwrap();
+
+    assert_eq!(schema.validate(&jsonway::object(|obj| {
+        obj.set("a", 1.0);
+        obj.array("b", |arr| { arr.push(2.0); });
+    }).unwrap()).is_valid(), true);
+
+    assert_eq!(schema.validate(&jsonway::object(|obj| {
+        obj.set("a", 1.5);
+        obj.array("b", |arr| { arr.push(2); });
+    }).unwrap()).is_valid(), false);
+}
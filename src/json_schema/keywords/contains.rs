@@ -0,0 +1,118 @@
+use serde_json::{Value};
+
+use super::super::schema;
+use super::super::validators;
+use super::super::helpers;
+
+#[allow(missing_copy_implementations)]
+pub struct Contains;
+impl super::Keyword for Contains {
+    fn compile(&self, def: &Value, ctx: &schema::WalkContext) -> super::KeywordResult {
+        let contains = keyword_key_exists!(def, "contains");
+
+        if ctx.is_schema(contains) {
+            let (min_contains, max_contains) = if ctx.draft >= super::super::Draft::V2019_09 {
+                (try!(contains_limit(def, "minContains", ctx)), try!(contains_limit(def, "maxContains", ctx)))
+            } else {
+                (None, None)
+            };
+
+            Ok(Some(Box::new(validators::Contains {
+                fragment: ctx.fragment.clone(),
+                link: ctx.link(helpers::alter_fragment_path(ctx.url.clone(), [
+                        ctx.escaped_fragment().as_ref(),
+                        "contains"
+                     ].join("/"))),
+                min_contains: min_contains,
+                max_contains: max_contains,
+                // Only since 2020-12 the matched items are considered evaluated
+                mark_evaluated: ctx.draft >= super::super::Draft::V2020_12
+            })))
+        } else {
+            Err(ctx.malformed("The value of `contains` MUST be a valid JSON Schema".to_string()))
+        }
+    }
+}
+
+fn contains_limit(def: &Value, key: &str, ctx: &schema::WalkContext) -> Result<Option<u64>, schema::SchemaError> {
+    match def.get(key) {
+        Some(limit) => {
+            match limit.as_f64() {
+                Some(limit) if limit >= 0f64 && limit.fract() == 0f64 => Ok(Some(limit as u64)),
+                _ => Err(ctx.malformed(format!("The value of `{}` MUST be a positive integer or zero", key)))
+            }
+        },
+        None => Ok(None)
+    }
+}
+
+#[cfg(test)] use super::super::scope;
+#[cfg(test)] use jsonway;
+#[cfg(test)] use super::super::builder;
+#[cfg(test)] use serde_json::to_value;
+
+#[test]
+fn validate() {
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(builder::schema(|s| {
+        s.schema("http://json-schema.org/draft-06/schema#");
+        s.contains(|contains| {
+            contains.minimum(5f64, false);
+        });
+    }).into_json(), true).ok().unwrap();
+
+    assert_eq!(schema.validate(&to_value(&[1, 2, 5]).unwrap()).is_valid(), true);
+    assert_eq!(schema.validate(&to_value(&[1, 2, 3]).unwrap()).is_valid(), false);
+    assert_eq!(schema.validate(&to_value(&Vec::<u64>::new()).unwrap()).is_valid(), false);
+    assert_eq!(schema.validate(&to_value(&"not an array").unwrap()).is_valid(), true);
+}
+
+#[test]
+fn validate_boolean() {
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(jsonway::object(|schema| {
+        schema.set("$schema", "http://json-schema.org/draft-06/schema#".to_string());
+        schema.set("contains", false);
+    }).unwrap(), true).ok().unwrap();
+
+    assert_eq!(schema.validate(&to_value(&[1]).unwrap()).is_valid(), false);
+}
+
+#[test]
+fn malformed() {
+    let mut scope = scope::Scope::new();
+
+    assert!(scope.compile_and_return(jsonway::object(|schema| {
+        schema.set("$schema", "http://json-schema.org/draft-06/schema#".to_string());
+        schema.set("contains", 1);
+    }).unwrap(), true).is_err());
+}
+
+#[test]
+fn validate_min_max_contains() {
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(jsonway::object(|schema| {
+        schema.set("$schema", "https://json-schema.org/draft/2019-09/schema".to_string());
+        schema.set("contains", builder::schema(|contains| { contains.minimum(5f64, false); }).into_json());
+        schema.set("minContains", 2);
+        schema.set("maxContains", 3);
+    }).unwrap(), true).ok().unwrap();
+
+    assert_eq!(schema.validate(&to_value(&[1, 5]).unwrap()).is_valid(), false);
+    assert_eq!(schema.validate(&to_value(&[1, 5, 6]).unwrap()).is_valid(), true);
+    assert_eq!(schema.validate(&to_value(&[5, 6, 7]).unwrap()).is_valid(), true);
+    assert_eq!(schema.validate(&to_value(&[5, 6, 7, 8]).unwrap()).is_valid(), false);
+}
+
+#[test]
+fn validate_min_contains_zero() {
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(jsonway::object(|schema| {
+        schema.set("$schema", "https://json-schema.org/draft/2019-09/schema".to_string());
+        schema.set("contains", builder::schema(|contains| { contains.minimum(5f64, false); }).into_json());
+        schema.set("minContains", 0);
+    }).unwrap(), true).ok().unwrap();
+
+    assert_eq!(schema.validate(&to_value(&Vec::<u64>::new()).unwrap()).is_valid(), true);
+    assert_eq!(schema.validate(&to_value(&[1, 2]).unwrap()).is_valid(), true);
+}
@@ -12,24 +12,21 @@ impl super::Keyword for Dependencies {
         let deps = keyword_key_exists!(def, "dependencies");
 
         if !deps.is_object() {
-            return Err(schema::SchemaError::Malformed {
-                path: ctx.fragment.join("/"),
-                detail: "The value of this keyword MUST be an object.".to_string()
-            })
+            return Err(ctx.malformed("The value of this keyword MUST be an object.".to_string()))
         }
 
         let deps = deps.as_object().unwrap();
         let mut items = collections::HashMap::new();
 
         for (key, item) in deps.iter() {
-            if item.is_object() {
+            if ctx.is_schema(item) {
 
                 items.insert(key.clone(), validators::dependencies::DepKind::Schema(
-                    helpers::alter_fragment_path(ctx.url.clone(), [
+                    ctx.link(helpers::alter_fragment_path(ctx.url.clone(), [
                         ctx.escaped_fragment().as_ref(),
                         "dependencies",
                         helpers::encode(key).as_ref()
-                    ].join("/"))
+                    ].join("/")))
                 ));
 
             } else if item.is_array() {
@@ -37,10 +34,7 @@ impl super::Keyword for Dependencies {
                 let item = item.as_array().unwrap();
 
                 if item.len() == 0 {
-                    return Err(schema::SchemaError::Malformed {
-                        path: ctx.fragment.join("/"),
-                        detail: "If the value is an array, it MUST have at least one element.".to_string()
-                    })
+                    return Err(ctx.malformed("If the value is an array, it MUST have at least one element.".to_string()))
                 }
 
                 let mut keys = vec![];
@@ -49,10 +43,7 @@ impl super::Keyword for Dependencies {
                     if key.is_string() {
                         keys.push(key.as_str().unwrap().to_string())
                     } else {
-                        return Err(schema::SchemaError::Malformed {
-                            path: ctx.fragment.join("/"),
-                            detail: "Each element MUST be a string, and elements in the array MUST be unique.".to_string()
-                        })
+                        return Err(ctx.malformed("Each element MUST be a string, and elements in the array MUST be unique.".to_string()))
                     }
                 }
 
@@ -61,21 +52,90 @@ impl super::Keyword for Dependencies {
                 ));
 
             } else {
-                return Err(schema::SchemaError::Malformed {
-                    path: ctx.fragment.join("/"),
-                    detail: "Each value of this object MUST be either an object or an array.".to_string()
-                })
+                return Err(ctx.malformed("Each value of this object MUST be either a valid JSON Schema or an array.".to_string()))
             }
         }
 
         Ok(Some(Box::new(validators::Dependencies {
             fragment: ctx.fragment.clone(),
+            keyword: "dependencies",
             items: items
         })))
 
     }
 }
 
+#[allow(missing_copy_implementations)]
+pub struct DependentSchemas;
+impl super::Keyword for DependentSchemas {
+    fn compile(&self, def: &Value, ctx: &schema::WalkContext) -> super::KeywordResult {
+        let deps = keyword_key_exists!(def, "dependentSchemas");
+
+        if !deps.is_object() {
+            return Err(ctx.malformed("The value of this keyword MUST be an object.".to_string()))
+        }
+
+        let deps = deps.as_object().unwrap();
+        let mut items = collections::HashMap::new();
+
+        for (key, item) in deps.iter() {
+            if ctx.is_schema(item) {
+                items.insert(key.clone(), validators::dependencies::DepKind::Schema(
+                    ctx.link(helpers::alter_fragment_path(ctx.url.clone(), [
+                        ctx.escaped_fragment().as_ref(),
+                        "dependentSchemas",
+                        helpers::encode(key).as_ref()
+                    ].join("/")))
+                ));
+            } else {
+                return Err(ctx.malformed("Each value of this object MUST be a valid JSON Schema.".to_string()))
+            }
+        }
+
+        Ok(Some(Box::new(validators::Dependencies {
+            fragment: ctx.fragment.clone(),
+            keyword: "dependentSchemas",
+            items: items
+        })))
+    }
+}
+
+#[allow(missing_copy_implementations)]
+pub struct DependentRequired;
+impl super::Keyword for DependentRequired {
+    fn compile(&self, def: &Value, ctx: &schema::WalkContext) -> super::KeywordResult {
+        let deps = keyword_key_exists!(def, "dependentRequired");
+
+        if !deps.is_object() {
+            return Err(ctx.malformed("The value of this keyword MUST be an object.".to_string()))
+        }
+
+        let deps = deps.as_object().unwrap();
+        let mut items = collections::HashMap::new();
+
+        for (key, item) in deps.iter() {
+            let keys = item.as_array().and_then(|item| {
+                item.iter().map(|key| key.as_str().map(|key| key.to_string())).collect::<Option<Vec<String>>>()
+            });
+
+            match keys {
+                Some(keys) => {
+                    items.insert(key.clone(), validators::dependencies::DepKind::Property(keys));
+                },
+                None => {
+                    return Err(ctx.malformed("Each value of this object MUST be an array of strings.".to_string()))
+                }
+            }
+        }
+
+        Ok(Some(Box::new(validators::Dependencies {
+            fragment: ctx.fragment.clone(),
+            keyword: "dependentRequired",
+            items: items
+        })))
+    }
+}
+
 #[cfg(test)] use super::super::scope;
 #[cfg(test)] use super::super::builder;
 #[cfg(test)] use jsonway;
@@ -140,3 +200,37 @@ fn malformed() {
         });
     }).unwrap(), true).is_err());
 }
+
+#[test]
+fn validate_dependent_schemas_and_required() {
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(jsonway::object(|schema| {
+        schema.set("$schema", "https://json-schema.org/draft/2019-09/schema".to_string());
+        schema.object("dependentSchemas", |deps| {
+            deps.set("isbn", builder::schema(|isbn| {
+                isbn.required(vec!["price".to_string()]);
+            }).into_json());
+        });
+        schema.object("dependentRequired", |deps| {
+            deps.array("item_id", |item_id| item_id.push("item_name".to_string()));
+        });
+    }).unwrap(), true).ok().unwrap();
+
+    assert_eq!(schema.validate(&jsonway::object(|obj| {
+        obj.set("isbn", "some_isbn".to_string());
+    }).unwrap()).is_valid(), false);
+
+    assert_eq!(schema.validate(&jsonway::object(|obj| {
+        obj.set("isbn", "some_isbn".to_string());
+        obj.set("price", 775);
+    }).unwrap()).is_valid(), true);
+
+    assert_eq!(schema.validate(&jsonway::object(|obj| {
+        obj.set("item_id", "some_id".to_string());
+    }).unwrap()).is_valid(), false);
+
+    assert_eq!(schema.validate(&jsonway::object(|obj| {
+        obj.set("item_id", "some_id".to_string());
+        obj.set("item_name", "some_name".to_string());
+    }).unwrap()).is_valid(), true);
+}
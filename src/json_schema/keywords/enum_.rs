@@ -13,21 +13,16 @@ impl super::Keyword for Enum {
             let enum_ = enum_.as_array().unwrap();
 
             if enum_.len() == 0 {
-                return Err(schema::SchemaError::Malformed {
-                    path: ctx.fragment.join("/"),
-                    detail: "This array MUST have at least one element.".to_string()
-                })
+                return Err(ctx.malformed("This array MUST have at least one element.".to_string()))
             }
 
             Ok(Some(Box::new(validators::Enum {
                 fragment: ctx.fragment.clone(),
+                keyword: "enum",
                 items: enum_.clone()
             })))
         } else {
-            Err(schema::SchemaError::Malformed {
-                path: ctx.fragment.join("/"),
-                detail: "The value of this keyword MUST be an array.".to_string()
-            })
+            Err(ctx.malformed("The value of this keyword MUST be an array.".to_string()))
         }
     }
 }
@@ -53,6 +48,21 @@ fn validate() {
     assert_eq!(schema.validate(&to_value(&1).unwrap()).is_valid(), false);
 }
 
+#[test]
+fn validate_numbers_by_value() {
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(builder::schema(|s| {
+        s.enum_(|items| {
+            items.push(1);
+            items.push(jsonway::array(|arr| { arr.push(2); }).unwrap());
+        })
+    }).into_json(), true).ok().unwrap();
+
+    assert_eq!(schema.validate(&to_value(&1.0).unwrap()).is_valid(), true);
+    assert_eq!(schema.validate(&to_value(&vec![2.0]).unwrap()).is_valid(), true);
+    assert_eq!(schema.validate(&to_value(&1.5).unwrap()).is_valid(), false);
+}
+
 #[test]
 fn malformed() {
     let mut scope = scope::Scope::new();
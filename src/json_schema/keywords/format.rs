@@ -4,6 +4,7 @@ use regex;
 
 use super::super::schema;
 use super::super::validators;
+use super::super::Draft;
 
 pub type FormatBuilders = collections::HashMap<String, Box<super::Keyword + Send + Sync>>;
 
@@ -13,46 +14,86 @@ lazy_static! {
     };
 }
 
+/// Adds the builder of the validator of `validators::formats` that checks the format.
+macro_rules! format_builder {
+    ($map:expr, $format:expr, $validator:ident) => {
+        $map.insert($format.to_string(), Box::new(|_def: &Value, ctx: &schema::WalkContext| {
+            Ok(Some(Box::new(validators::formats::$validator {
+                fragment: ctx.fragment.clone(),
+                keyword: "format",
+            }) as validators::BoxedValidator))
+        }));
+    }
+}
+
 fn default_formats() -> FormatBuilders  {
     let mut map: FormatBuilders = collections::HashMap::new();
 
-    let date_time_builder = Box::new(|_def: &Value, ctx: &schema::WalkContext| {
-        Ok(Some(Box::new(validators::Pattern{
-            fragment: ctx.fragment.clone(),
-            regex: DATE_TIME_REGEX.clone()
-        }) as validators::BoxedValidator))
-    });
-    map.insert("date-time".to_string(), date_time_builder);
+    format_builder!(map, "date-time", DateTime);
+    format_builder!(map, "ipv4", Ipv4);
+    format_builder!(map, "ipv6", Ipv6);
+    format_builder!(map, "uri", Uri);
+    format_builder!(map, "uuid", Uuid);
+    format_builder!(map, "email", Email);
+    format_builder!(map, "idn-email", IdnEmail);
+    format_builder!(map, "hostname", Hostname);
+    format_builder!(map, "idn-hostname", IdnHostname);
+    format_builder!(map, "date", Date);
+    format_builder!(map, "time", Time);
+    format_builder!(map, "duration", Duration);
+    format_builder!(map, "uri-reference", UriReference);
+    format_builder!(map, "iri", Iri);
+    format_builder!(map, "iri-reference", IriReference);
+    format_builder!(map, "uri-template", UriTemplate);
+    format_builder!(map, "json-pointer", JsonPointer);
+    format_builder!(map, "relative-json-pointer", RelativeJsonPointer);
+    format_builder!(map, "regex", Regex);
 
-    let ipv4_builder = Box::new(|_def: &Value, ctx: &schema::WalkContext| {
-        Ok(Some(Box::new(validators::formats::Ipv4 {
-            fragment: ctx.fragment.clone(),
-        }) as validators::BoxedValidator))
-    });
-    map.insert("ipv4".to_string(), ipv4_builder);
-
-    let ipv6_builder = Box::new(|_def: &Value, ctx: &schema::WalkContext| {
-        Ok(Some(Box::new(validators::formats::Ipv6 {
-            fragment: ctx.fragment.clone(),
-        }) as validators::BoxedValidator))
-    });
-    map.insert("ipv6".to_string(), ipv6_builder);
+    map
+}
 
-    let uri_builder = Box::new(|_def: &Value, ctx: &schema::WalkContext| {
-        Ok(Some(Box::new(validators::formats::Uri {
+/// Replaces the RFC 3339 `date-time` format with the lenient one of the previous
+/// versions, which accepts offsets without colons and named time zones but doesn't
+/// check that the date exists:
+///
+/// ```rust
+/// # use valico::json_schema;
+/// let scope = json_schema::Scope::with_formats(json_schema::keywords::format::use_lenient_date_time);
+/// ```
+pub fn use_lenient_date_time(formats: &mut FormatBuilders) {
+    formats.insert("date-time".to_string(), Box::new(|_def: &Value, ctx: &schema::WalkContext| {
+        Ok(Some(Box::new(validators::Pattern{
             fragment: ctx.fragment.clone(),
+            keyword: "format",
+            regex: DATE_TIME_REGEX.clone()
         }) as validators::BoxedValidator))
-    });
-    map.insert("uri".to_string(), uri_builder);
+    }));
+}
 
-    let uuid_builder = Box::new(|_def: &Value, ctx: &schema::WalkContext| {
-        Ok(Some(Box::new(validators::formats::Uuid {
-            fragment: ctx.fragment.clone(),
-        }) as validators::BoxedValidator))
-    });
-    map.insert("uuid".to_string(), uuid_builder);
+/// How the `format` keyword is handled.
+///
+/// Since 2019-09 formats are only annotations unless the format-assertion vocabulary
+/// is used, so valico asserts them by default only in the previous drafts.
+#[derive(Copy, Debug, Clone, PartialEq, Eq)]
+pub enum FormatMode {
+    /// Known formats are validated, unknown ones are ignored.
+    Assert,
+    /// Formats are only collected as annotations.
+    Annotate,
+    /// Known formats are validated, unknown ones are compile errors.
+    Strict
+}
 
-    map
+impl FormatMode {
+    /// The mode of the schemas of the draft when none is set: `Annotate` since
+    /// 2019-09 and `Assert` before.
+    pub fn default_for(draft: Draft) -> FormatMode {
+        if draft >= Draft::V2019_09 {
+            FormatMode::Annotate
+        } else {
+            FormatMode::Assert
+        }
+    }
 }
 
 #[allow(missing_copy_implementations)]
@@ -82,19 +123,22 @@ impl super::Keyword for Format {
 
         if format.is_string() {
             let format = format.as_str().unwrap();
-            match self.formats.get(format) {
-                Some(keyword) => {
+            match (self.formats.get(format), ctx.format_mode) {
+                (_, FormatMode::Annotate) => Ok(None),
+                (Some(keyword), _) => {
                     keyword.compile(def, ctx)
                 },
-                None => {
+                (None, FormatMode::Strict) => Err(schema::SchemaError::UnknownFormat {
+                    url: (**ctx.document).clone(),
+                    pointer: ctx.pointer(),
+                    format: format.to_string()
+                }),
+                (None, _) => {
                     Ok(None)
                 }
             }
         } else {
-            Err(schema::SchemaError::Malformed {
-                path: ctx.fragment.join("/"),
-                detail: "The value of format MUST be a string".to_string()
-            })
+            Err(ctx.malformed("The value of format MUST be a string".to_string()))
         }
     }
 }
@@ -110,8 +154,26 @@ fn validate_date_time() {
         s.format("date-time");
     }).into_json(), true).ok().unwrap();
 
-    assert_eq!(schema.validate(&to_value(&"2015-01-20T17:35:20-0800").unwrap()).is_valid(), true);
+    assert_eq!(schema.validate(&to_value(&"2015-01-20T17:35:20-08:00").unwrap()).is_valid(), true);
     assert_eq!(schema.validate(&to_value(&"1944-06-06T04:04:00Z").unwrap()).is_valid(), true);
+    assert_eq!(schema.validate(&to_value(&"1963-06-19t08:30:06.283185z").unwrap()).is_valid(), true);
+    assert_eq!(schema.validate(&to_value(&"2016-02-29T23:59:60Z").unwrap()).is_valid(), true);
+    assert_eq!(schema.validate(&to_value(&"1998-12-31T15:59:60.123-08:00").unwrap()).is_valid(), true);
+    assert_eq!(schema.validate(&to_value(&"Tue, 20 Jan 2015 17:35:20 -0800").unwrap()).is_valid(), false);
+    assert_eq!(schema.validate(&to_value(&"2015-01-20T17:35:20-0800").unwrap()).is_valid(), false);
+    assert_eq!(schema.validate(&to_value(&"2015-02-31T25:61:61Z").unwrap()).is_valid(), false);
+    assert_eq!(schema.validate(&to_value(&"2015-02-29T12:00:00Z").unwrap()).is_valid(), false);
+    assert_eq!(schema.validate(&to_value(&"2015-01-20T17:35:20PST").unwrap()).is_valid(), false);
+    assert_eq!(schema.validate(&to_value(&"2015-01-20T23:59:60+01:00").unwrap()).is_valid(), false);
+    assert_eq!(schema.validate(&to_value(&"2015-01-20T17:35:20").unwrap()).is_valid(), false);
+
+    let mut scope = scope::Scope::with_formats(use_lenient_date_time);
+    let schema = scope.compile_and_return(builder::schema(|s| {
+        s.format("date-time");
+    }).into_json(), true).ok().unwrap();
+
+    assert_eq!(schema.validate(&to_value(&"2015-01-20T17:35:20-0800").unwrap()).is_valid(), true);
+    assert_eq!(schema.validate(&to_value(&"2015-01-20T17:35:20PST").unwrap()).is_valid(), true);
     assert_eq!(schema.validate(&to_value(&"Tue, 20 Jan 2015 17:35:20 -0800").unwrap()).is_valid(), false);
 }
 
@@ -160,3 +222,155 @@ fn validate_uuid() {
     assert_eq!(schema.validate(&to_value(&"2f5a2593748149e299118fe2ad069aac").unwrap()).is_valid(), true);
     assert_eq!(schema.validate(&to_value(&"2f5a2593-7481-49e2-9911-8fe2ad06").unwrap()).is_valid(), false);
 }
+
+#[cfg(test)]
+fn assert_format(format: &str, valid: &[&str], invalid: &[&str]) {
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(builder::schema(|s| {
+        s.format(format);
+    }).into_json(), true).ok().unwrap();
+
+    for value in valid.iter() {
+        assert!(schema.validate(&to_value(value).unwrap()).is_valid(), "{} should be a valid {}", value, format);
+    }
+    for value in invalid.iter() {
+        assert!(!schema.validate(&to_value(value).unwrap()).is_valid(), "{} should be an invalid {}", value, format);
+    }
+}
+
+#[test]
+fn validate_email() {
+    assert_format("email",
+        &["joe.bloggs@example.com", "te~st@example.com", "\"joe bloggs\"@example.com", "joe.bloggs@[127.0.0.1]", "joe.bloggs@[IPv6:::1]"],
+        &["2962", ".test@example.com", "test.@example.com", "te..st@example.com", "joe.bloggs@invalid=domain.com", "joe.bloggs@[127.0.0.300]", "\u{444}@example.com"]);
+    assert_format("idn-email",
+        &["실례@실례.테스트", "joe.bloggs@example.com"],
+        &["2962", "실례@실례@테스트"]);
+}
+
+#[test]
+fn validate_hostname() {
+    assert_format("hostname",
+        &["www.example.com", "xn--4gbwdl.xn--wgbh1c", "1host", "hostname", "a-b"],
+        &["-a-host-name-that-starts-with--", "not_a_valid_host_name", "", ".", "example..com",
+          "a-vvvvvvvvvvvvvvvveeeeeeeeeeeeeeeerrrrrrrrrrrrrrrryyyyyyyyyyyyyyyy-long-host-name-component", "XN--aa---o47jg78q"]);
+    assert_format("idn-hostname",
+        &["실례.테스트", "l\u{b7}l", "\u{3b1}\u{375}\u{3b2}", "\u{5d0}\u{5f3}\u{5d1}", "\u{30fb}\u{3041}", "\u{915}\u{94d}\u{200d}\u{937}"],
+        &["〮실례.테스트", "-> $1.00 <-", "a\u{b7}l", "\u{3b1}\u{375}S", "\u{5f3}\u{5d1}", "def\u{30fb}abc", "\u{915}\u{200d}\u{937}",
+          "\u{660}\u{6f0}"]);
+}
+
+#[test]
+fn validate_date_and_time() {
+    assert_format("date",
+        &["1963-06-19", "2020-01-31", "2020-02-29"],
+        &["2020-02-30", "2021-02-29", "2020-13-01", "06/19/1963", "2013-350", "1998-1-20", "1963-06-1\u{9ea}"]);
+    assert_format("time",
+        &["08:30:06Z", "08:30:06.283185Z", "23:59:60Z", "15:59:60-08:00", "01:29:60+01:30", "08:30:06+00:20", "08:30:06z"],
+        &["22:59:60Z", "23:59:60+01:00", "008:030:006Z", "8:3:6Z", "24:00:00Z", "08:30:06 PST", "01:01:01,1111", "08:30:06", "08:30:06+0100"]);
+}
+
+#[test]
+fn validate_duration() {
+    assert_format("duration",
+        &["P4DT12H30M5S", "P4Y", "PT0S", "P0D", "P1M", "PT36H", "P2W", "P1Y2M", "PT1M5S"],
+        &["PT1D", "P", "P1YT", "PT", "P2D1Y", "P1D2H", "P1Y2W", "P\u{9e8}Y", "4DT12H30M5S", "P1Y1D"]);
+}
+
+#[test]
+fn validate_uri_references() {
+    assert_format("uri-reference",
+        &["http://foo.bar/?baz=qux#quux", "//foo.bar/?baz=qux#quux", "/abc", "#fragment", "abc#fragment", "http://[2001:db8::1]:80/", "urn:isbn:0451450523"],
+        &["\\\\WINDOWS\\fileshare", "#frag\\ment", "http://example.com/a b", "1a:b", "http://example.com/%2x", "http://[::1/"]);
+    assert_format("iri",
+        &["http://ƒøø.ßår/?∂éœ=πîx#πîüx", "http://[2001:0db8:85a3:0000:0000:8a2e:0370:7334]"],
+        &["http://2001:0db8:85a3:0000:0000:8a2e:0370:7334", "/abc", "\\\\WINDOWS\\filëßåré", "âππ"]);
+    assert_format("iri-reference",
+        &["http://ƒøø.ßår/?∂éœ=πîx#πîüx", "//ƒøø.ßår/?∂éœ=πîx#πîüx", "/âππ", "âππ", "#ƒrägmênt"],
+        &["\\\\WINDOWS\\filëßåré", "#ƒräg\\mênt"]);
+}
+
+#[test]
+fn validate_uri_template() {
+    assert_format("uri-template",
+        &["http://example.com/dictionary/{term:1}/{term}", "http://example.com/dictionary", "dictionary/{term:1}/{term}", "{/path*}{?x,y}"],
+        &["http://example.com/dictionary/{term:1}/{term", "{term:01}", "{te rm}", "{}", "a}b"]);
+}
+
+#[test]
+fn validate_json_pointers() {
+    assert_format("json-pointer",
+        &["", "/foo/bar~0/baz~1/%a", "/", "/foo//bar", "/~0~1"],
+        &["/foo/bar~", "#", "a", "/~2"]);
+    assert_format("relative-json-pointer",
+        &["1", "0/foo/bar", "2/0/baz/1/zip", "0#", "120/foo/bar"],
+        &["/foo/bar", "-1/foo/bar", "+1/foo/bar", "0##", "01/a", "01#", ""]);
+}
+
+#[test]
+fn validate_regex() {
+    assert_format("regex", &["([abc])+\\s+$", r"(?<name>a)\k<name>", r"\cC\p{Letter}"], &["^(abc]", r"\a", r"(?i)a"]);
+}
+
+#[test]
+fn format_modes() {
+    let mut scope = scope::Scope::new();
+    scope.set_format_mode(FormatMode::Annotate);
+    let schema = scope.compile_and_return(builder::schema(|s| {
+        s.format("email");
+    }).into_json(), true).ok().unwrap();
+
+    let state = schema.annotate(&to_value(&"not an email").unwrap());
+    assert!(state.is_valid());
+    assert_eq!(state.annotations[0].keyword, "format");
+    assert_eq!(state.annotations[0].value, to_value(&"email").unwrap());
+
+    let mut scope = scope::Scope::new();
+    scope.set_format_mode(FormatMode::Strict);
+    assert!(scope.compile(builder::schema(|s| s.format("email")).into_json(), true).is_ok());
+
+    let error = scope.compile(builder::schema(|s| {
+        s.properties(|props| props.insert("color", |color| color.format("color")));
+    }).into_json(), true).err().unwrap();
+    match error {
+        schema::SchemaError::UnknownFormat { ref pointer, ref format, .. } => {
+            assert_eq!(pointer, "/properties/color");
+            assert_eq!(format, "color");
+        },
+        _ => panic!("Expected an unknown format error, got {:?}", error)
+    }
+
+    // Unknown formats are ignored by default
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(builder::schema(|s| s.format("color")).into_json(), true).ok().unwrap();
+    assert!(schema.validate(&to_value(&"red").unwrap()).is_valid());
+}
+
+#[test]
+fn format_mode_defaults_to_draft() {
+    let drafts = [
+        ("http://json-schema.org/draft-04/schema#", false),
+        ("http://json-schema.org/draft-06/schema#", false),
+        ("http://json-schema.org/draft-07/schema#", false),
+        ("https://json-schema.org/draft/2019-09/schema", true),
+        ("https://json-schema.org/draft/2020-12/schema", true)
+    ];
+
+    for &(url, annotation) in drafts.iter() {
+        let mut scope = scope::Scope::new();
+        let schema = scope.compile_and_return(builder::schema(|s| {
+            s.schema(url);
+            s.format("email");
+        }).into_json(), true).ok().unwrap();
+        assert_eq!(schema.validate(&to_value(&"not an email").unwrap()).is_valid(), annotation, "{}", url);
+
+        // A mode set on the scope applies to every draft
+        let mut scope = scope::Scope::new();
+        scope.set_format_mode(FormatMode::Assert);
+        let schema = scope.compile_and_return(builder::schema(|s| {
+            s.schema(url);
+            s.format("email");
+        }).into_json(), true).ok().unwrap();
+        assert!(!schema.validate(&to_value(&"not an email").unwrap()).is_valid(), "{}", url);
+    }
+}
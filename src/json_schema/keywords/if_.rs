@@ -0,0 +1,117 @@
+use serde_json::{Value};
+
+use super::super::schema;
+use super::super::validators;
+use super::super::helpers;
+
+#[allow(missing_copy_implementations)]
+pub struct If;
+impl super::Keyword for If {
+    fn compile(&self, def: &Value, ctx: &schema::WalkContext) -> super::KeywordResult {
+        let if_ = keyword_key_exists!(def, "if");
+
+        let link = |key: &str, value: &Value| {
+            if ctx.is_schema(value) {
+                Ok(ctx.link(helpers::alter_fragment_path(ctx.url.clone(), [
+                    ctx.escaped_fragment().as_ref(),
+                    key
+                ].join("/"))))
+            } else {
+                Err(ctx.malformed(format!("The value of `{}` MUST be a valid JSON Schema", key)))
+            }
+        };
+
+        let if_link = try!(link("if", if_));
+        let then_link = match def.get("then") {
+            Some(then) => Some(try!(link("then", then))),
+            None => None
+        };
+        let else_link = match def.get("else") {
+            Some(else_) => Some(try!(link("else", else_))),
+            None => None
+        };
+
+        // Since 2019-09 a valid `if` contributes to the evaluated properties and items
+        // even without `then` and `else`.
+        if then_link.is_none() && else_link.is_none() && ctx.draft < super::super::Draft::V2019_09 {
+            return Ok(None)
+        }
+
+        Ok(Some(Box::new(validators::If {
+            link: if_link,
+            then_link: then_link,
+            else_link: else_link
+        })))
+    }
+}
+
+#[cfg(test)] use super::super::scope;
+#[cfg(test)] use jsonway;
+#[cfg(test)] use super::super::builder;
+#[cfg(test)] use serde_json::to_value;
+
+#[test]
+fn validate_if_then_else() {
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(builder::schema(|s| {
+        s.schema("http://json-schema.org/draft-07/schema#");
+        s.if_(|if_| {
+            if_.maximum(10f64, false);
+        });
+        s.then(|then| {
+            then.multiple_of(2f64);
+        });
+        s.else_(|else_| {
+            else_.multiple_of(5f64);
+        });
+    }).into_json(), true).ok().unwrap();
+
+    assert_eq!(schema.validate(&to_value(4).unwrap()).is_valid(), true);
+    assert_eq!(schema.validate(&to_value(3).unwrap()).is_valid(), false);
+    assert_eq!(schema.validate(&to_value(15).unwrap()).is_valid(), true);
+    assert_eq!(schema.validate(&to_value(12).unwrap()).is_valid(), false);
+    assert_eq!(schema.validate(&to_value("not a number").unwrap()).is_valid(), true);
+}
+
+#[test]
+fn validate_without_else() {
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(builder::schema(|s| {
+        s.schema("http://json-schema.org/draft-07/schema#");
+        s.if_(|if_| {
+            if_.maximum(10f64, false);
+        });
+        s.then(|then| {
+            then.multiple_of(2f64);
+        });
+    }).into_json(), true).ok().unwrap();
+
+    assert_eq!(schema.validate(&to_value(4).unwrap()).is_valid(), true);
+    assert_eq!(schema.validate(&to_value(3).unwrap()).is_valid(), false);
+    assert_eq!(schema.validate(&to_value(13).unwrap()).is_valid(), true);
+}
+
+#[test]
+fn malformed() {
+    let mut scope = scope::Scope::new();
+
+    assert!(scope.compile_and_return(jsonway::object(|schema| {
+        schema.set("$schema", "http://json-schema.org/draft-07/schema#".to_string());
+        schema.set("if", 10);
+        schema.object("then", |_| {});
+    }).unwrap(), true).is_err());
+}
+
+#[test]
+fn ignored_before_draft7() {
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(jsonway::object(|schema| {
+        schema.set("$schema", "http://json-schema.org/draft-06/schema#".to_string());
+        schema.object("if", |_| {});
+        schema.object("then", |then| {
+            then.set("multipleOf", 2);
+        });
+    }).unwrap(), false).ok().unwrap();
+
+    assert_eq!(schema.validate(&to_value(3).unwrap()).is_valid(), true);
+}
@@ -17,32 +17,29 @@ impl super::Keyword for Items {
 
         let items = if maybe_items.is_some() {
             let items_val = maybe_items.unwrap();
-            Some(if items_val.is_object() {
+            Some(if ctx.is_schema(items_val) {
 
                 validators::items::ItemsKind::Schema(
-                    helpers::alter_fragment_path(ctx.url.clone(), [
+                    ctx.link(helpers::alter_fragment_path(ctx.url.clone(), [
                         ctx.escaped_fragment().as_ref(),
                         "items"
-                    ].join("/"))
+                    ].join("/")))
                 )
 
             } else if items_val.is_array() {
 
                 let mut schemas = vec![];
                 for (idx, item) in items_val.as_array().unwrap().iter().enumerate() {
-                    if item.is_object() {
+                    if ctx.is_schema(item) {
                         schemas.push(
-                            helpers::alter_fragment_path(ctx.url.clone(), [
+                            ctx.link(helpers::alter_fragment_path(ctx.url.clone(), [
                                 ctx.escaped_fragment().as_ref(),
                                 "items",
                                 idx.to_string().as_ref()
-                            ].join("/"))
+                            ].join("/")))
                         )
                     } else {
-                        return Err(schema::SchemaError::Malformed {
-                            path: ctx.fragment.join("/"),
-                            detail: "Items of this array MUST be objects".to_string()
-                        })
+                        return Err(ctx.malformed("Items of this array MUST be valid JSON Schemas".to_string()))
                     }
                 }
 
@@ -50,10 +47,7 @@ impl super::Keyword for Items {
 
             } else {
 
-                return Err(schema::SchemaError::Malformed {
-                    path: ctx.fragment.join("/"),
-                    detail: "`items` must be an object or an array".to_string()
-                })
+                return Err(ctx.malformed("`items` must be an object or an array".to_string()))
 
             })
         } else {
@@ -69,18 +63,15 @@ impl super::Keyword for Items {
             } else if additional_val.is_object() {
 
                 validators::items::AdditionalKind::Schema(
-                    helpers::alter_fragment_path(ctx.url.clone(), [
+                    ctx.link(helpers::alter_fragment_path(ctx.url.clone(), [
                         ctx.escaped_fragment().as_ref(),
                         "additionalItems"
-                    ].join("/"))
+                    ].join("/")))
                 )
 
             } else {
 
-                return Err(schema::SchemaError::Malformed {
-                    path: ctx.fragment.join("/"),
-                    detail: "`additionalItems` must be a boolean or an object".to_string()
-                })
+                return Err(ctx.malformed("`additionalItems` must be a boolean or an object".to_string()))
 
             })
         } else {
@@ -89,15 +80,87 @@ impl super::Keyword for Items {
 
         Ok(Some(Box::new(validators::Items {
             fragment: ctx.fragment.clone(),
+            keyword: "additionalItems",
             items: items,
-            additional: additional_items
+            additional: additional_items,
+            mark_evaluated: ctx.draft >= super::super::Draft::V2019_09
         })))
 
     }
 }
 
+/// `prefixItems` and `items` as defined by JSON Schema 2020-12, where `items` applies
+/// to the items that aren't covered by `prefixItems`.
+#[allow(missing_copy_implementations)]
+pub struct PrefixItems;
+impl super::Keyword for PrefixItems {
+    fn compile(&self, def: &Value, ctx: &schema::WalkContext) -> super::KeywordResult {
+        let maybe_prefix = def.get("prefixItems");
+        let maybe_items = def.get("items");
+
+        if !(maybe_prefix.is_some() || maybe_items.is_some()) {
+            return Ok(None)
+        }
+
+        let mut prefix = vec![];
+        if maybe_prefix.is_some() {
+            let prefix_val = maybe_prefix.unwrap();
+            if !prefix_val.is_array() || prefix_val.as_array().unwrap().len() == 0 {
+                return Err(ctx.malformed("`prefixItems` MUST be a non-empty array".to_string()))
+            }
+
+            for (idx, item) in prefix_val.as_array().unwrap().iter().enumerate() {
+                if ctx.is_schema(item) {
+                    prefix.push(
+                        ctx.link(helpers::alter_fragment_path(ctx.url.clone(), [
+                            ctx.escaped_fragment().as_ref(),
+                            "prefixItems",
+                            idx.to_string().as_ref()
+                        ].join("/")))
+                    )
+                } else {
+                    return Err(ctx.malformed("Items of `prefixItems` MUST be valid JSON Schemas".to_string()))
+                }
+            }
+        }
+
+        let additional_items = if maybe_items.is_some() {
+            let items_val = maybe_items.unwrap();
+            Some(if items_val.is_boolean() {
+
+                validators::items::AdditionalKind::Boolean(items_val.as_bool().unwrap())
+
+            } else if items_val.is_object() {
+
+                validators::items::AdditionalKind::Schema(
+                    ctx.link(helpers::alter_fragment_path(ctx.url.clone(), [
+                        ctx.escaped_fragment().as_ref(),
+                        "items"
+                    ].join("/")))
+                )
+
+            } else {
+
+                return Err(ctx.malformed("`items` MUST be a valid JSON Schema".to_string()))
+
+            })
+        } else {
+            None
+        };
+
+        Ok(Some(Box::new(validators::Items {
+            fragment: ctx.fragment.clone(),
+            keyword: "items",
+            items: Some(validators::items::ItemsKind::Array(prefix)),
+            additional: additional_items,
+            mark_evaluated: ctx.draft >= super::super::Draft::V2019_09
+        })))
+    }
+}
+
 #[cfg(test)] use super::super::scope;
 #[cfg(test)] use super::super::builder;
+#[cfg(test)] use jsonway;
 #[cfg(test)] use serde_json::to_value;
 
 #[test]
@@ -181,3 +244,67 @@ fn validate_items_with_array_of_schemes_with_additional_schema() {
     assert_eq!(schema.validate(&to_value(&[1,3,100]).unwrap()).is_valid(), true);
     assert_eq!(schema.validate(&to_value(&[1,3,101]).unwrap()).is_valid(), false);
 }
+
+#[test]
+fn validate_prefix_items() {
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(jsonway::object(|schema| {
+        schema.set("$schema", "https://json-schema.org/draft/2020-12/schema".to_string());
+        schema.array("prefixItems", |items| {
+            items.push(builder::schema(|item| { item.minimum(1f64, false); item.maximum(3f64, false); }).into_json());
+            items.push(builder::schema(|item| { item.minimum(3f64, false); item.maximum(6f64, false); }).into_json());
+        });
+        schema.set("items", builder::schema(|item| { item.maximum(100f64, false); }).into_json());
+    }).unwrap(), true).ok().unwrap();
+
+    assert_eq!(schema.validate(&to_value(&[1]).unwrap()).is_valid(), true);
+    assert_eq!(schema.validate(&to_value(&[1,3,100]).unwrap()).is_valid(), true);
+    assert_eq!(schema.validate(&to_value(&[4,3]).unwrap()).is_valid(), false);
+    assert_eq!(schema.validate(&to_value(&[1,3,101]).unwrap()).is_valid(), false);
+}
+
+#[test]
+fn validate_items_without_prefix_items() {
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(jsonway::object(|schema| {
+        schema.set("$schema", "https://json-schema.org/draft/2020-12/schema".to_string());
+        schema.set("items", builder::schema(|item| { item.maximum(10f64, false); }).into_json());
+    }).unwrap(), true).ok().unwrap();
+
+    assert_eq!(schema.validate(&to_value(&[1,10]).unwrap()).is_valid(), true);
+    assert_eq!(schema.validate(&to_value(&[1,11]).unwrap()).is_valid(), false);
+
+    assert!(scope.compile(jsonway::object(|schema| {
+        schema.set("$schema", "https://json-schema.org/draft/2020-12/schema".to_string());
+        schema.array("items", |items| {
+            items.push(builder::schema(|item| { item.maximum(10f64, false); }).into_json());
+        });
+    }).unwrap(), true).is_err());
+}
+
+#[test]
+fn validate_boolean_items() {
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(jsonway::object(|schema| {
+        schema.set("$schema", "http://json-schema.org/draft-06/schema#".to_string());
+        schema.array("items", |items| {
+            items.push(true);
+            items.push(false);
+        });
+    }).unwrap(), true).ok().unwrap();
+
+    assert_eq!(schema.validate(&to_value(&[1]).unwrap()).is_valid(), true);
+    assert_eq!(schema.validate(&to_value(&[1,2]).unwrap()).is_valid(), false);
+
+    let schema = scope.compile_and_return(jsonway::object(|schema| {
+        schema.set("$schema", "http://json-schema.org/draft-06/schema#".to_string());
+        schema.set("items", false);
+    }).unwrap(), true).ok().unwrap();
+
+    assert_eq!(schema.validate(&to_value(&Vec::<u64>::new()).unwrap()).is_valid(), true);
+    assert_eq!(schema.validate(&to_value(&[1]).unwrap()).is_valid(), false);
+
+    assert!(scope.compile_and_return(jsonway::object(|schema| {
+        schema.set("items", false);
+    }).unwrap(), true).is_err());
+}
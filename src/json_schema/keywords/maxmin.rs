@@ -10,39 +10,32 @@ macro_rules! kw_minmax{
         impl super::Keyword for $name {
             fn compile(&self, def: &Value, ctx: &schema::WalkContext) -> super::KeywordResult {
                 let maybe_value = def.get($keyword);
-                let exclusive = def.get($exclusive);
+                // Since Draft 6 `exclusiveMinimum/exclusiveMaximum` are standalone numeric keywords
+                let exclusive = if ctx.draft == super::super::Draft::V4 { def.get($exclusive) } else { None };
 
                 if exclusive.is_some() {
                     if !maybe_value.is_some() {
-                        return Err(schema::SchemaError::Malformed {
-                            path: ctx.fragment.join("/"),
-                            detail: "`exclusiveMinimum/exclusiveMaximum` can't go without minimum/maximum".to_string()
-                        })
+                        return Err(ctx.malformed("`exclusiveMinimum/exclusiveMaximum` can't go without minimum/maximum".to_string()))
                     }
                 }
 
                 if maybe_value.is_some() {
                     let value = maybe_value.unwrap();
-                    if value.is_number() {
-                        let value = value.as_f64().unwrap();
+                    if let Value::Number(ref value) = *value {
                         Ok(Some(Box::new(validators::$name {
                             fragment: ctx.fragment.clone(),
-                            number: value,
+                            keyword: $keyword,
+                            number: value.as_f64().unwrap(),
+                            limit: value.clone(),
                             exclusive: exclusive.is_some() &&
                                        try!(exclusive.unwrap()
                                             .as_bool()
                                             .ok_or_else(||
-                                                schema::SchemaError::Malformed {
-                                                    path: ctx.fragment.join("/"),
-                                                    detail: "`exclusiveMaximum/exclusiveMaximum` must be boolean".to_string()
-                                                }
+                                                ctx.malformed("`exclusiveMaximum/exclusiveMaximum` must be boolean".to_string())
                                             ))
                         })))
                     } else {
-                        Err(schema::SchemaError::Malformed {
-                            path: ctx.fragment.join("/"),
-                            detail: "the `minimum/maximum` value must be a number".to_string()
-                        })
+                        Err(ctx.malformed("the `minimum/maximum` value must be a number".to_string()))
                     }
                 } else {
                     Ok(None)
@@ -55,10 +48,38 @@ macro_rules! kw_minmax{
 kw_minmax!(Minimum, "minimum", "exclusiveMinimum");
 kw_minmax!(Maximum, "maximum", "exclusiveMaximum");
 
+macro_rules! kw_exclusive{
+    ($name:ident, $keyword:expr, $validator:ident) => {
+        #[allow(missing_copy_implementations)]
+        pub struct $name;
+        impl super::Keyword for $name {
+            fn compile(&self, def: &Value, ctx: &schema::WalkContext) -> super::KeywordResult {
+                let value = keyword_key_exists!(def, $keyword);
+
+                if let Value::Number(ref value) = *value {
+                    Ok(Some(Box::new(validators::$validator {
+                        fragment: ctx.fragment.clone(),
+                        keyword: $keyword,
+                        number: value.as_f64().unwrap(),
+                        limit: value.clone(),
+                        exclusive: true
+                    })))
+                } else {
+                    Err(ctx.malformed("the `exclusiveMinimum/exclusiveMaximum` value must be a number".to_string()))
+                }
+            }
+        }
+    }
+}
+
+kw_exclusive!(ExclusiveMinimum, "exclusiveMinimum", Minimum);
+kw_exclusive!(ExclusiveMaximum, "exclusiveMaximum", Maximum);
+
 #[cfg(test)] use super::super::scope;
 #[cfg(test)] use jsonway;
 #[cfg(test)] use super::super::builder;
 #[cfg(test)] use serde_json::to_value;
+#[cfg(test)] use super::super::super::common::catalog::English;
 
 #[test]
 fn validate_maximum() {
@@ -153,3 +174,76 @@ fn mailformed_exclusive_minumum() {
         schema.set("exclusiveMinimum", "".to_string());
     }).unwrap(), true).is_err());
 }
+
+#[test]
+fn validate_exclusive_maximum_draft6() {
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(jsonway::object(|schema| {
+        schema.set("$schema", "http://json-schema.org/draft-06/schema#".to_string());
+        schema.set("maximum", 10);
+        schema.set("exclusiveMaximum", 5);
+    }).unwrap(), true).ok().unwrap();
+
+    assert_eq!(schema.validate(&to_value(&4).unwrap()).is_valid(), true);
+    assert_eq!(schema.validate(&to_value(&5).unwrap()).is_valid(), false);
+    assert_eq!(schema.validate(&to_value(&11).unwrap()).is_valid(), false);
+}
+
+#[test]
+fn validate_exclusive_minimum_draft6() {
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(jsonway::object(|schema| {
+        schema.set("$schema", "http://json-schema.org/draft-06/schema#".to_string());
+        schema.set("exclusiveMinimum", 5);
+    }).unwrap(), true).ok().unwrap();
+
+    assert_eq!(schema.validate(&to_value(&5).unwrap()).is_valid(), false);
+    assert_eq!(schema.validate(&to_value(&6).unwrap()).is_valid(), true);
+}
+
+#[test]
+fn mailformed_exclusive_minimum_draft6() {
+    let mut scope = scope::Scope::new();
+
+    assert!(scope.compile_and_return(jsonway::object(|schema| {
+        schema.set("$schema", "http://json-schema.org/draft-06/schema#".to_string());
+        schema.set("minimum", 10);
+        schema.set("exclusiveMinimum", true);
+    }).unwrap(), true).is_err());
+}
+
+#[test]
+fn maximum_error_params() {
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(builder::schema(|s| {
+        s.maximum(10f64, true);
+    }).into_json(), true).ok().unwrap();
+
+    let state = schema.validate(&to_value(&12).unwrap());
+    let params = state.errors[0].get_params();
+    assert_eq!(params["limit"], to_value(10.0).unwrap());
+    assert_eq!(params["exclusive"], to_value(true).unwrap());
+    assert_eq!(params["actual"], to_value(12).unwrap());
+
+    let serialized = to_value(&state).unwrap();
+    assert_eq!(serialized["errors"][0]["params"]["limit"], to_value(10.0).unwrap());
+
+    let mut catalog = ::std::collections::HashMap::new();
+    catalog.insert("maximum".to_string(), "must be less than {limit}, got {actual}".to_string());
+    assert_eq!(state.localize(&catalog)[0]["message"], "must be less than 10, got 12");
+    assert_eq!(state.localize(&English)[0]["message"], "The value must be less than 10, got 12");
+}
+
+#[test]
+fn integer_error_params() {
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(jsonway::object(|schema| {
+        schema.set("minimum", 9007199254740993u64);
+    }).unwrap(), true).ok().unwrap();
+
+    // Integers keep their exact value, beyond the precision of floats
+    let state = schema.validate(&to_value(&10).unwrap());
+    let serialized = to_value(&state).unwrap();
+    assert_eq!(serialized["errors"][0]["params"]["limit"], to_value(9007199254740993u64).unwrap());
+    assert_eq!(serialized["errors"][0]["params"]["actual"], to_value(10).unwrap());
+}
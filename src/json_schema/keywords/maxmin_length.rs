@@ -16,19 +16,14 @@ macro_rules! kw_minmax_integer{
                     if length_val >= 0f64 && length_val.fract() == 0f64 {
                         Ok(Some(Box::new(validators::$name {
                             fragment: ctx.fragment.clone(),
+                            keyword: $keyword,
                             length: length_val as u64
                         })))
                     } else {
-                        Err(schema::SchemaError::Malformed {
-                            path: ctx.fragment.join("/"),
-                            detail: "The value MUST be a positive integer or zero".to_string()
-                        })
+                        Err(ctx.malformed("The value MUST be a positive integer or zero".to_string()))
                     }
                 } else {
-                    Err(schema::SchemaError::Malformed {
-                        path: ctx.fragment.join("/"),
-                        detail: "The value MUST be a positive integer or zero".to_string()
-                    })
+                    Err(ctx.malformed("The value MUST be a positive integer or zero".to_string()))
                 }
             }
         }
@@ -1,6 +1,6 @@
 use serde_json::{Value};
 use std::fmt;
-use std::rc;
+use std::sync;
 use std::collections;
 use std::any;
 
@@ -10,9 +10,9 @@ use super::validators;
 pub type KeywordResult = Result<Option<validators::BoxedValidator>, schema::SchemaError>;
 pub type KeywordPair = (Vec<&'static str>, Box<Keyword + 'static>);
 pub type KeywordPairs = Vec<KeywordPair>;
-pub type KeywordMap = collections::HashMap<&'static str, rc::Rc<KeywordConsumer>>;
+pub type KeywordMap = collections::HashMap<&'static str, sync::Arc<KeywordConsumer>>;
 
-pub trait Keyword: Sync + any::Any {
+pub trait Keyword: Send + Sync + any::Any {
     fn compile(&self, &Value, &schema::WalkContext) -> KeywordResult;
 }
 
@@ -57,6 +57,11 @@ pub mod of;
 pub mod ref_;
 pub mod not;
 pub mod format;
+pub mod const_;
+pub mod contains;
+pub mod property_names;
+pub mod if_;
+pub mod unevaluated;
 
 pub fn default() -> KeywordMap {
     let mut map = collections::HashMap::new();
@@ -87,6 +92,68 @@ pub fn default() -> KeywordMap {
     map
 }
 
+/// Keywords of JSON Schema Draft 6, which extends Draft 4.
+pub fn draft6() -> KeywordMap {
+    let mut map = default();
+
+    decouple_keyword((vec!["maximum"], Box::new(maxmin::Maximum)), &mut map);
+    decouple_keyword((vec!["exclusiveMaximum"], Box::new(maxmin::ExclusiveMaximum)), &mut map);
+    decouple_keyword((vec!["minimum"], Box::new(maxmin::Minimum)), &mut map);
+    decouple_keyword((vec!["exclusiveMinimum"], Box::new(maxmin::ExclusiveMinimum)), &mut map);
+    decouple_keyword((vec!["const"], Box::new(const_::Const)), &mut map);
+    decouple_keyword((vec!["contains"], Box::new(contains::Contains)), &mut map);
+    decouple_keyword((vec!["propertyNames"], Box::new(property_names::PropertyNames)), &mut map);
+
+    map
+}
+
+/// Keywords of JSON Schema Draft 7, which extends Draft 6.
+pub fn draft7() -> KeywordMap {
+    let mut map = draft6();
+
+    decouple_keyword((vec!["if", "then", "else"], Box::new(if_::If)), &mut map);
+
+    map
+}
+
+/// Keywords of JSON Schema 2019-09, which extends Draft 7.
+pub fn draft2019_09() -> KeywordMap {
+    let mut map = draft7();
+
+    decouple_keyword((vec!["contains", "minContains", "maxContains"], Box::new(contains::Contains)), &mut map);
+    decouple_keyword((vec!["dependentSchemas"], Box::new(dependencies::DependentSchemas)), &mut map);
+    decouple_keyword((vec!["dependentRequired"], Box::new(dependencies::DependentRequired)), &mut map);
+    decouple_keyword((vec!["unevaluatedProperties"], Box::new(unevaluated::UnevaluatedProperties)), &mut map);
+    decouple_keyword((vec!["unevaluatedItems"], Box::new(unevaluated::UnevaluatedItems)), &mut map);
+    decouple_keyword((vec!["$recursiveRef"], Box::new(ref_::RecursiveRef)), &mut map);
+
+    map
+}
+
+/// Keywords of JSON Schema 2020-12, which replaces the array form of `items`
+/// and `additionalItems` of 2019-09 with `prefixItems` and `items`, and
+/// `$recursiveRef` with `$dynamicRef`.
+pub fn draft2020_12() -> KeywordMap {
+    let mut map = draft2019_09();
+
+    map.remove("additionalItems");
+    map.remove("$recursiveRef");
+    decouple_keyword((vec!["$dynamicRef"], Box::new(ref_::DynamicRef)), &mut map);
+    decouple_keyword((vec!["prefixItems", "items"], Box::new(items::PrefixItems)), &mut map);
+
+    map
+}
+
+pub fn for_draft(draft: super::Draft) -> KeywordMap {
+    match draft {
+        super::Draft::V4 => default(),
+        super::Draft::V6 => draft6(),
+        super::Draft::V7 => draft7(),
+        super::Draft::V2019_09 => draft2019_09(),
+        super::Draft::V2020_12 => draft2020_12(),
+    }
+}
+
 #[derive(Debug)]
 pub struct KeywordConsumer {
     pub keys: Vec<&'static str>,
@@ -106,8 +173,11 @@ impl KeywordConsumer {
 pub fn decouple_keyword(keyword_pair: KeywordPair,
                         map: &mut KeywordMap) {
     let (keys, keyword) = keyword_pair;
-    let consumer = rc::Rc::new(KeywordConsumer { keys: keys.clone(), keyword: keyword });
-    for key in keys.iter() {
+    insert_consumer(sync::Arc::new(KeywordConsumer { keys: keys, keyword: keyword }), map);
+}
+
+pub fn insert_consumer(consumer: sync::Arc<KeywordConsumer>, map: &mut KeywordMap) {
+    for key in consumer.keys.iter() {
         map.insert(key, consumer.clone());
     }
 }
@@ -9,24 +9,20 @@ impl super::Keyword for MultipleOf {
     fn compile(&self, def: &Value, ctx: &schema::WalkContext) -> super::KeywordResult {
         let multiple_of = keyword_key_exists!(def, "multipleOf");
 
-        if multiple_of.is_number() {
-            let multiple_of = multiple_of.as_f64().unwrap();
-            if multiple_of > 0f64 {
+        if let Value::Number(ref multiple_of) = *multiple_of {
+            let number = multiple_of.as_f64().unwrap();
+            if number > 0f64 {
                 Ok(Some(Box::new(validators::MultipleOf {
                     fragment: ctx.fragment.clone(),
-                    number: multiple_of
+                    keyword: "multipleOf",
+                    number: number,
+                    multiple_of: multiple_of.clone()
                 })))
             } else {
-                Err(schema::SchemaError::Malformed {
-                    path: ctx.fragment.join("/"),
-                    detail: "The value of multipleOf MUST be strictly greater than 0".to_string()
-                })
+                Err(ctx.malformed("The value of multipleOf MUST be strictly greater than 0".to_string()))
             }
         } else {
-            Err(schema::SchemaError::Malformed {
-                path: ctx.fragment.join("/"),
-                detail: "The value of multipleOf MUST be a JSON number".to_string()
-            })
+            Err(ctx.malformed("The value of multipleOf MUST be a JSON number".to_string()))
         }
     }
 }
@@ -10,19 +10,17 @@ impl super::Keyword for Not {
     fn compile(&self, def: &Value, ctx: &schema::WalkContext) -> super::KeywordResult {
         let not = keyword_key_exists!(def, "not");
 
-        if not.is_object() {
+        if ctx.is_schema(not) {
             Ok(Some(Box::new(validators::Not {
                 fragment: ctx.fragment.clone(),
-                url: helpers::alter_fragment_path(ctx.url.clone(), [
+                keyword: "not",
+                link: ctx.link(helpers::alter_fragment_path(ctx.url.clone(), [
                         ctx.escaped_fragment().as_ref(),
                         "not"
-                     ].join("/"))
+                     ].join("/")))
             })))
         } else {
-            Err(schema::SchemaError::Malformed {
-                path: ctx.fragment.join("/"),
-                detail: "The value of `not` MUST be an object".to_string()
-            })
+            Err(ctx.malformed("The value of `not` MUST be a valid JSON Schema".to_string()))
         }
     }
 }
@@ -5,7 +5,7 @@ use super::super::validators;
 use super::super::helpers;
 
 macro_rules! of_keyword{
-    ($name:ident, $kw:expr) => {
+    ($name:ident, $kw:expr $(, $field:ident: $value:expr)*) => {
 
         #[allow(missing_copy_implementations)]
         pub struct $name;
@@ -17,39 +17,32 @@ macro_rules! of_keyword{
                     let of = of.as_array().unwrap();
 
                     if of.len() == 0 {
-                        return Err(schema::SchemaError::Malformed {
-                            path: ctx.fragment.join("/"),
-                            detail: "This array MUST have at least one element.".to_string()
-                        })
+                        return Err(ctx.malformed("This array MUST have at least one element.".to_string()))
                     }
 
                     let mut schemes = vec![];
                     for (idx, scheme) in of.iter().enumerate() {
-                        if scheme.is_object() {
+                        if ctx.is_schema(scheme) {
                             schemes.push(
-                                helpers::alter_fragment_path(ctx.url.clone(), [
+                                ctx.link(helpers::alter_fragment_path(ctx.url.clone(), [
                                     ctx.escaped_fragment().as_ref(),
                                     $kw,
                                     idx.to_string().as_ref()
-                                ].join("/"))
+                                ].join("/")))
                             )
                         } else {
-                            return Err(schema::SchemaError::Malformed {
-                                path: ctx.fragment.join("/"),
-                                detail: "Elements of the array MUST be objects.".to_string()
-                            })
+                            return Err(ctx.malformed("Elements of the array MUST be valid JSON Schemas.".to_string()))
                         }
                     }
 
                     Ok(Some(Box::new(validators::$name {
                         fragment: ctx.fragment.clone(),
+                        keyword: $kw,
                         schemes: schemes
+                        $(, $field: $value(ctx))*
                     })))
                 } else {
-                    Err(schema::SchemaError::Malformed {
-                        path: ctx.fragment.join("/"),
-                        detail: "The value of this keyword MUST be an array.".to_string()
-                    })
+                    Err(ctx.malformed("The value of this keyword MUST be an array.".to_string()))
                 }
             }
         }
@@ -58,11 +51,13 @@ macro_rules! of_keyword{
 }
 
 of_keyword!(AllOf, "allOf");
-of_keyword!(AnyOf, "anyOf");
+// All the branches of `anyOf` are only evaluated when `unevaluated*` keywords can see them
+of_keyword!(AnyOf, "anyOf", mark_evaluated: |ctx: &schema::WalkContext| ctx.draft >= super::super::Draft::V2019_09);
 of_keyword!(OneOf, "oneOf");
 
 #[cfg(test)] use super::super::scope;
 #[cfg(test)] use super::super::builder;
+#[cfg(test)] use jsonway;
 #[cfg(test)] use serde_json::to_value;
 
 #[test]
@@ -121,3 +116,32 @@ fn validate_one_of() {
     assert_eq!(schema.validate(&to_value(&6).unwrap()).is_valid(), true);
     assert_eq!(schema.validate(&to_value(&11).unwrap()).is_valid(), false);
 }
+
+#[test]
+fn validate_boolean_schemes() {
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(jsonway::object(|schema| {
+        schema.set("$schema", "http://json-schema.org/draft-06/schema#".to_string());
+        schema.array("anyOf", |any_of| {
+            any_of.push(false);
+            any_of.push(true);
+        });
+        schema.array("oneOf", |one_of| {
+            one_of.push(true);
+            one_of.push(false);
+        });
+        schema.set("not", false);
+    }).unwrap(), true).ok().unwrap();
+
+    assert_eq!(schema.validate(&to_value(1).unwrap()).is_valid(), true);
+
+    let schema = scope.compile_and_return(jsonway::object(|schema| {
+        schema.set("$schema", "http://json-schema.org/draft-06/schema#".to_string());
+        schema.array("allOf", |all_of| {
+            all_of.push(true);
+            all_of.push(false);
+        });
+    }).unwrap(), true).ok().unwrap();
+
+    assert_eq!(schema.validate(&to_value(1).unwrap()).is_valid(), false);
+}
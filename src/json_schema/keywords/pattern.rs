@@ -1,6 +1,5 @@
 use serde_json::{Value};
-use regex;
-
+use super::super::ecma_regex;
 use super::super::schema;
 use super::super::validators;
 
@@ -12,21 +11,19 @@ impl super::Keyword for Pattern {
 
         if pattern.is_string() {
             let pattern_val = pattern.as_str().unwrap();
-            match regex::Regex::new(pattern_val) {
+            match ecma_regex::compile_with_size_limit(pattern_val, ctx.limits.regex_size) {
                 Ok(re) => Ok(Some(Box::new(validators::Pattern {
                     fragment: ctx.fragment.clone(),
+                    keyword: "pattern",
                     regex: re
                 }))),
-                Err(err) => Err(schema::SchemaError::Malformed {
-                    path: ctx.fragment.join("/"),
-                    detail: format!("The value of pattern MUST be a valid RegExp, but {:?}", err)
-                }),
+                Err(ecma_regex::RegexError::Syntax(detail)) => {
+                    Err(ctx.malformed(format!("The value of pattern MUST be a valid ECMA-262 regular expression, but {}", detail)))
+                },
+                Err(err) => Err(ctx.regex_error(pattern_val, err)),
             }
         } else {
-            Err(schema::SchemaError::Malformed {
-                path: ctx.fragment.join("/"),
-                detail: "The value of pattern MUST be a string".to_string()
-            })
+            Err(ctx.malformed("The value of pattern MUST be a string".to_string()))
         }
     }
 }
@@ -60,3 +57,24 @@ fn mailformed() {
         schema.set("pattern", 2);
     }).unwrap(), true).is_err());
 }
+
+#[test]
+fn ecma_semantics() {
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(jsonway::object(|schema| {
+        schema.set("pattern", r"^\d+$".to_string());
+    }).unwrap(), true).ok().unwrap();
+
+    assert_eq!(schema.validate(&to_value("42").unwrap()).is_valid(), true);
+    assert_eq!(schema.validate(&to_value("\u{0664}\u{0662}").unwrap()).is_valid(), false);
+
+    let error = scope.compile_and_return(jsonway::object(|schema| {
+        schema.set("pattern", r"^(a)\1$".to_string());
+    }).unwrap(), true).err().unwrap();
+
+    match error {
+        schema::SchemaError::UnsupportedRegex { ref pattern, .. } => assert_eq!(pattern, r"^(a)\1$"),
+        ref other => panic!("unexpected error {:?}", other)
+    }
+    assert!(error.to_string().ends_with(r"can't use the regular expression `^(a)\1$`: backreferences are not supported"));
+}
@@ -1,7 +1,7 @@
 use std::collections;
-use regex;
 use serde_json::{Value};
 
+use super::super::ecma_regex;
 use super::super::schema;
 use super::super::validators;
 use super::super::helpers;
@@ -24,27 +24,21 @@ impl super::Keyword for Properties {
                 let mut schemes = collections::HashMap::new();
                 let properties = properties.as_object().unwrap();
                 for (key, value) in properties.iter() {
-                    if value.is_object() {
+                    if ctx.is_schema(value) {
                         schemes.insert(key.to_string(),
-                            helpers::alter_fragment_path(ctx.url.clone(), [
+                            ctx.link(helpers::alter_fragment_path(ctx.url.clone(), [
                                 ctx.escaped_fragment().as_ref(),
                                 "properties",
                                 helpers::encode(key).as_ref()
-                            ].join("/"))
+                            ].join("/")))
                         );
                     } else {
-                        return Err(schema::SchemaError::Malformed {
-                            path: ctx.fragment.join("/"),
-                            detail: "Each value of this object MUST be an object".to_string()
-                        })
+                        return Err(ctx.malformed("Each value of this object MUST be a valid JSON Schema".to_string()))
                     }
                 }
                 schemes
             } else {
-                return Err(schema::SchemaError::Malformed {
-                    path: ctx.fragment.join("/"),
-                    detail: "The value of `properties` MUST be an object.".to_string()
-                })
+                return Err(ctx.malformed("The value of `properties` MUST be an object.".to_string()))
             }
         } else {
             collections::HashMap::new()
@@ -52,29 +46,26 @@ impl super::Keyword for Properties {
 
         let additional_properties = if maybe_additional.is_some() {
             let additional_val = maybe_additional.unwrap();
-            if additional_val.is_boolean() {
+            Some(if additional_val.is_boolean() {
 
                 validators::properties::AdditionalKind::Boolean(additional_val.as_bool().unwrap())
 
             } else if additional_val.is_object() {
 
                 validators::properties::AdditionalKind::Schema(
-                    helpers::alter_fragment_path(ctx.url.clone(), [
+                    ctx.link(helpers::alter_fragment_path(ctx.url.clone(), [
                         ctx.escaped_fragment().as_ref(),
                         "additionalProperties"
-                    ].join("/"))
+                    ].join("/")))
                 )
 
             } else {
 
-                return Err(schema::SchemaError::Malformed {
-                    path: ctx.fragment.join("/"),
-                    detail: "The value of `additionalProperties` MUST be a boolean or an object.".to_string()
-                })
+                return Err(ctx.malformed("The value of `additionalProperties` MUST be a boolean or an object.".to_string()))
 
-            }
+            })
         } else {
-            validators::properties::AdditionalKind::Boolean(true)
+            None
         };
 
         let patterns = if maybe_pattern.is_some() {
@@ -84,48 +75,42 @@ impl super::Keyword for Properties {
                 let mut patterns = vec![];
 
                 for (key, value) in pattern.iter() {
-                    if value.is_object() {
+                    if ctx.is_schema(value) {
 
-                        match regex::Regex::new(key.as_ref()) {
+                        match ecma_regex::compile_with_size_limit(key.as_ref(), ctx.limits.regex_size) {
                             Ok(regex) => {
-                                let url = helpers::alter_fragment_path(ctx.url.clone(), [
+                                let link = ctx.link(helpers::alter_fragment_path(ctx.url.clone(), [
                                     ctx.escaped_fragment().as_ref(),
                                     "patternProperties",
                                     helpers::encode(key).as_ref()
-                                ].join("/"));
-                                patterns.push((regex, url));
+                                ].join("/")));
+                                patterns.push((regex, link));
+                            },
+                            Err(ecma_regex::RegexError::Syntax(_)) => {
+                                return Err(ctx.malformed("Each property name of this object SHOULD be a valid regular expression.".to_string()))
                             },
-                            Err(_) => {
-                                return Err(schema::SchemaError::Malformed {
-                                    path: ctx.fragment.join("/"),
-                                    detail: "Each property name of this object SHOULD be a valid regular expression.".to_string()
-                                })
-                            }
+                            Err(err) => return Err(ctx.regex_error(key, err))
                         }
 
                     } else {
-                        return Err(schema::SchemaError::Malformed {
-                            path: ctx.fragment.join("/"),
-                            detail: "Each value of this object MUST be an object".to_string()
-                        })
+                        return Err(ctx.malformed("Each value of this object MUST be a valid JSON Schema".to_string()))
                     }
                 }
 
                 patterns
 
             } else {
-                return Err(schema::SchemaError::Malformed {
-                    path: ctx.fragment.join("/"),
-                    detail: "The value of `patternProperties` MUST be an object".to_string()
-                })
+                return Err(ctx.malformed("The value of `patternProperties` MUST be an object".to_string()))
             }
         } else { vec![] };
 
         Ok(Some(Box::new(validators::Properties {
             fragment: ctx.fragment.clone(),
+            keyword: "additionalProperties",
             properties: properties,
             additional: additional_properties,
-            patterns: patterns
+            patterns: patterns,
+            mark_evaluated: ctx.draft >= super::super::Draft::V2019_09
         })))
 
     }
@@ -317,3 +302,54 @@ fn malformed() {
         schema.set("additionalProperties", 10);
     }).unwrap(), true).is_err());
 }
+
+#[test]
+fn validate_boolean_properties() {
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(jsonway::object(|schema| {
+        schema.set("$schema", "http://json-schema.org/draft-06/schema#".to_string());
+        schema.object("properties", |properties| {
+            properties.set("allowed", true);
+            properties.set("forbidden", false);
+        });
+        schema.object("patternProperties", |properties| {
+            properties.set("^x-", false);
+        });
+    }).unwrap(), true).ok().unwrap();
+
+    assert_eq!(schema.validate(&jsonway::object(|obj| {
+        obj.set("allowed", 1);
+    }).unwrap()).is_valid(), true);
+
+    assert_eq!(schema.validate(&jsonway::object(|obj| {
+        obj.set("forbidden", 1);
+    }).unwrap()).is_valid(), false);
+
+    assert_eq!(schema.validate(&jsonway::object(|obj| {
+        obj.set("x-extension", 1);
+    }).unwrap()).is_valid(), false);
+}
+
+#[test]
+fn validate_ecma_pattern_properties() {
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(jsonway::object(|schema| {
+        schema.object("patternProperties", |properties| {
+            properties.object(r"^\w+$", |schema| { schema.set("type", "integer".to_string()); });
+        });
+    }).unwrap(), true).ok().unwrap();
+
+    assert_eq!(schema.validate(&jsonway::object(|obj| {
+        obj.set("key_1", 1);
+        obj.set("é", "not validated".to_string());
+    }).unwrap()).is_valid(), true);
+
+    assert!(match scope.compile_and_return(jsonway::object(|schema| {
+        schema.object("patternProperties", |properties| {
+            properties.object("^x-(?=y)", |_| {});
+        });
+    }).unwrap(), true) {
+        Err(schema::SchemaError::UnsupportedRegex { .. }) => true,
+        _ => false
+    });
+}
@@ -0,0 +1,65 @@
+use serde_json::{Value};
+
+use super::super::schema;
+use super::super::validators;
+use super::super::helpers;
+
+#[allow(missing_copy_implementations)]
+pub struct PropertyNames;
+impl super::Keyword for PropertyNames {
+    fn compile(&self, def: &Value, ctx: &schema::WalkContext) -> super::KeywordResult {
+        let property_names = keyword_key_exists!(def, "propertyNames");
+
+        if ctx.is_schema(property_names) {
+            Ok(Some(Box::new(validators::PropertyNames {
+                fragment: ctx.fragment.clone(),
+                keyword: "propertyNames",
+                link: ctx.link(helpers::alter_fragment_path(ctx.url.clone(), [
+                        ctx.escaped_fragment().as_ref(),
+                        "propertyNames"
+                     ].join("/")))
+            })))
+        } else {
+            Err(ctx.malformed("The value of `propertyNames` MUST be a valid JSON Schema".to_string()))
+        }
+    }
+}
+
+#[cfg(test)] use super::super::scope;
+#[cfg(test)] use jsonway;
+#[cfg(test)] use super::super::builder;
+
+#[test]
+fn validate() {
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(builder::schema(|s| {
+        s.schema("http://json-schema.org/draft-06/schema#");
+        s.property_names(|names| {
+            names.max_length(3u64);
+        });
+    }).into_json(), true).ok().unwrap();
+
+    assert_eq!(schema.validate(&jsonway::object(|obj| {
+        obj.set("foo", 1);
+        obj.set("bar", 1);
+    }).unwrap()).is_valid(), true);
+
+    assert_eq!(schema.validate(&jsonway::object(|obj| {
+        obj.set("foo", 1);
+        obj.set("barbaz", 1);
+    }).unwrap()).is_valid(), false);
+}
+
+#[test]
+fn validate_false() {
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(jsonway::object(|schema| {
+        schema.set("$schema", "http://json-schema.org/draft-06/schema#".to_string());
+        schema.set("propertyNames", false);
+    }).unwrap(), true).ok().unwrap();
+
+    assert_eq!(schema.validate(&jsonway::object(|_obj| {}).unwrap()).is_valid(), true);
+    assert_eq!(schema.validate(&jsonway::object(|obj| {
+        obj.set("foo", 1);
+    }).unwrap()).is_valid(), false);
+}
@@ -16,27 +16,67 @@ impl super::Keyword for Ref {
                 Ok(url) => {
                     Ok(Some(Box::new(validators::Ref {
                         fragment: ctx.fragment.clone(),
-                        url: url
+                        keyword: "$ref",
+                        link: ctx.link(url)
                     })))
                 },
                 Err(_) => {
-                    Err(schema::SchemaError::Malformed {
-                        path: ctx.fragment.join("/"),
-                        detail: "The value of $ref MUST be an URI-encoded JSON Pointer".to_string()
-                    })
+                    Err(ctx.malformed("The value of $ref MUST be an URI-encoded JSON Pointer".to_string()))
                 }
             }
         } else {
-            Err(schema::SchemaError::Malformed {
-                path: ctx.fragment.join("/"),
-                detail: "The value of multipleOf MUST be a string".to_string()
-            })
+            Err(ctx.malformed("The value of multipleOf MUST be a string".to_string()))
         }
     }
 }
 
+#[allow(missing_copy_implementations)]
+pub struct RecursiveRef;
+impl super::Keyword for RecursiveRef {
+    fn compile(&self, def: &Value, ctx: &schema::WalkContext) -> super::KeywordResult {
+        let url = try!(parse_ref(keyword_key_exists!(def, "$recursiveRef"), "$recursiveRef", ctx));
+
+        Ok(Some(Box::new(validators::RecursiveRef {
+            fragment: ctx.fragment.clone(),
+            keyword: "$recursiveRef",
+            link: ctx.link(url)
+        })))
+    }
+}
+
+#[allow(missing_copy_implementations)]
+pub struct DynamicRef;
+impl super::Keyword for DynamicRef {
+    fn compile(&self, def: &Value, ctx: &schema::WalkContext) -> super::KeywordResult {
+        let url = try!(parse_ref(keyword_key_exists!(def, "$dynamicRef"), "$dynamicRef", ctx));
+
+        // Only plain-name fragments can refer to a `$dynamicAnchor`
+        let anchor = url.fragment()
+            .and_then(|fragment| if fragment.len() > 0 && !fragment.starts_with("/") { Some(fragment.to_string()) } else { None });
+
+        Ok(Some(Box::new(validators::DynamicRef {
+            fragment: ctx.fragment.clone(),
+            keyword: "$dynamicRef",
+            link: ctx.link(url),
+            anchor: anchor
+        })))
+    }
+}
+
+fn parse_ref(ref_: &Value, key: &str, ctx: &schema::WalkContext) -> Result<Url, schema::SchemaError> {
+    match ref_.as_str() {
+        Some(ref_) => {
+            Url::options().base_url(Some(ctx.url)).parse(ref_).map_err(|_| {
+                ctx.malformed(format!("The value of {} MUST be an URI reference", key))
+            })
+        },
+        None => Err(ctx.malformed(format!("The value of {} MUST be a string", key)))
+    }
+}
+
 #[cfg(test)] use super::super::scope;
 #[cfg(test)] use super::super::builder;
+#[cfg(test)] use jsonway;
 #[cfg(test)] use serde_json::to_value;
 
 #[test]
@@ -60,3 +100,121 @@ fn validate() {
     assert_eq!(schema.validate(&to_value(&array3).unwrap()).is_valid(), false);
     assert_eq!(schema.validate(&to_value(&vec![1,2]).unwrap()).is_valid(), false);
 }
+
+#[test]
+fn validate_recursive_ref() {
+    let mut scope = scope::Scope::new();
+
+    scope.compile(jsonway::object(|schema| {
+        schema.set("$schema", "https://json-schema.org/draft/2019-09/schema".to_string());
+        schema.set("$id", "http://example.com/tree".to_string());
+        schema.set("$recursiveAnchor", true);
+        schema.set("type", "object".to_string());
+        schema.object("properties", |props| {
+            props.object("children", |children| {
+                children.set("type", "array".to_string());
+                children.object("items", |items| {
+                    items.set("$recursiveRef", "#".to_string());
+                });
+            });
+        });
+    }).unwrap(), true).ok().unwrap();
+
+    let schema = scope.compile_and_return(jsonway::object(|schema| {
+        schema.set("$schema", "https://json-schema.org/draft/2019-09/schema".to_string());
+        schema.set("$id", "http://example.com/strict-tree".to_string());
+        schema.set("$recursiveAnchor", true);
+        schema.set("$ref", "tree".to_string());
+        schema.set("unevaluatedProperties", false);
+    }).unwrap(), true).ok().unwrap();
+
+    assert_eq!(schema.validate(&jsonway::object(|obj| {
+        obj.array("children", |children| {
+            children.push(jsonway::object(|child| {
+                child.array("children", |_| {});
+            }).unwrap());
+        });
+    }).unwrap()).is_strictly_valid(), true);
+
+    // The nested object is validated against the strict tree as well
+    assert_eq!(schema.validate(&jsonway::object(|obj| {
+        obj.array("children", |children| {
+            children.push(jsonway::object(|child| {
+                child.set("daat", 1);
+            }).unwrap());
+        });
+    }).unwrap()).is_valid(), false);
+}
+
+#[test]
+fn validate_dynamic_ref() {
+    let mut scope = scope::Scope::new();
+
+    scope.compile(jsonway::object(|schema| {
+        schema.set("$schema", "https://json-schema.org/draft/2020-12/schema".to_string());
+        schema.set("$id", "http://example.com/list".to_string());
+        schema.set("type", "array".to_string());
+        schema.object("items", |items| {
+            items.set("$dynamicRef", "#item".to_string());
+        });
+        schema.object("$defs", |defs| {
+            defs.object("item", |item| {
+                item.set("$dynamicAnchor", "item".to_string());
+            });
+        });
+    }).unwrap(), true).ok().unwrap();
+
+    let schema = scope.compile_and_return(jsonway::object(|schema| {
+        schema.set("$schema", "https://json-schema.org/draft/2020-12/schema".to_string());
+        schema.set("$id", "http://example.com/string-list".to_string());
+        schema.set("$ref", "list".to_string());
+        schema.object("$defs", |defs| {
+            defs.object("string", |string| {
+                string.set("$dynamicAnchor", "item".to_string());
+                string.set("type", "string".to_string());
+            });
+        });
+    }).unwrap(), true).ok().unwrap();
+
+    assert_eq!(schema.validate(&to_value(&["a", "b"]).unwrap()).is_strictly_valid(), true);
+    assert_eq!(schema.validate(&to_value(&[1, 2]).unwrap()).is_valid(), false);
+
+    // Without the outer resource the anchor of the list itself is used
+    let list = scope.resolve(&Url::parse("http://example.com/list").unwrap()).unwrap();
+    assert_eq!(list.validate(&to_value(&[1, 2]).unwrap()).is_strictly_valid(), true);
+}
+
+#[test]
+fn error_locations_follow_refs() {
+    let mut scope = scope::Scope::new();
+
+    scope.compile(jsonway::object(|schema| {
+        schema.set("id", "http://example.com/item".to_string());
+        schema.object("definitions", |definitions| {
+            definitions.object("positive", |positive| positive.set("minimum", 0));
+        });
+    }).unwrap(), true).ok().unwrap();
+
+    let schema = scope.compile_and_return(jsonway::object(|schema| {
+        schema.set("id", "http://example.com/order".to_string());
+        schema.object("properties", |props| {
+            props.object("amount", |amount| amount.set("$ref", "item#/definitions/positive".to_string()));
+            props.object("count", |count| count.set("maximum", 10));
+        });
+    }).unwrap(), true).ok().unwrap();
+
+    let state = schema.validate(&jsonway::object(|obj| {
+        obj.set("amount", -1);
+        obj.set("count", 11);
+    }).unwrap());
+
+    let mut locations = state.errors.iter().map(|error| {
+        (error.get_keyword_location().unwrap().join("/"), error.get_absolute_keyword_location().unwrap().to_string())
+    }).collect::<Vec<(String, String)>>();
+    locations.sort();
+
+    assert_eq!(locations, vec![
+        ("properties/amount/$ref/minimum".to_string(), "http://example.com/item#/definitions/positive/minimum".to_string()),
+        ("properties/count/maximum".to_string(), "http://example.com/order#/properties/count/maximum".to_string())
+    ]);
+}
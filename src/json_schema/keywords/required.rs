@@ -13,10 +13,7 @@ impl super::Keyword for Required {
             let required = required.as_array().unwrap();
 
             if required.len() == 0 {
-                return Err(schema::SchemaError::Malformed {
-                    path: ctx.fragment.join("/"),
-                    detail: "This array MUST have at least one element.".to_string()
-                })
+                return Err(ctx.malformed("This array MUST have at least one element.".to_string()))
             }
 
             let mut items = vec![];
@@ -24,22 +21,17 @@ impl super::Keyword for Required {
                 if item.is_string() {
                     items.push(item.as_str().unwrap().to_string())
                 } else {
-                    return Err(schema::SchemaError::Malformed {
-                        path: ctx.fragment.join("/"),
-                        detail: "The values of `required` MUST be strings".to_string()
-                    })
+                    return Err(ctx.malformed("The values of `required` MUST be strings".to_string()))
                 }
             }
 
             Ok(Some(Box::new(validators::Required {
                 fragment: ctx.fragment.clone(),
+                keyword: "required",
                 items: items
             })))
         } else {
-            Err(schema::SchemaError::Malformed {
-                path: ctx.fragment.join("/"),
-                detail: "The value of this keyword MUST be an array.".to_string()
-            })
+            Err(ctx.malformed("The value of this keyword MUST be an array.".to_string()))
         }
     }
 }
@@ -15,26 +15,21 @@ impl super::Keyword for Type {
             if ty.is_some() {
                 Ok(Some(Box::new(validators::Type {
                     fragment: ctx.fragment.clone(),
+                    keyword: "type",
                     item: validators::type_::TypeKind::Single(ty.unwrap())
                 })))
             } else {
-                Err(schema::SchemaError::Malformed {
-                    path: ctx.fragment.join("/"),
-                    detail: format!(
-                        "String values MUST be one of the seven primitive types defined by the core specification. Unknown type: {}",
-                        type_.as_str().unwrap()
-                    )
-                })
+                Err(ctx.malformed(format!(
+                    "String values MUST be one of the seven primitive types defined by the core specification. Unknown type: {}",
+                    type_.as_str().unwrap()
+                )))
             }
 
         } else if type_.is_array() {
             let types = type_.as_array().unwrap();
 
             if types.len() == 0 {
-                return Err(schema::SchemaError::Malformed {
-                    path: ctx.fragment.join("/"),
-                    detail: "This array MUST have at least one element.".to_string()
-                })
+                return Err(ctx.malformed("This array MUST have at least one element.".to_string()))
             }
 
             let mut converted_types = vec![];
@@ -44,28 +39,20 @@ impl super::Keyword for Type {
                     if converted_ty.is_some() {
                         converted_types.push(converted_ty.unwrap());
                     } else {
-                        return Err(schema::SchemaError::Malformed {
-                            path: ctx.fragment.join("/"),
-                            detail: format!("Unknown type: {}", ty.as_str().unwrap())
-                        })
+                        return Err(ctx.malformed(format!("Unknown type: {}", ty.as_str().unwrap())))
                     }
                 } else {
-                    return Err(schema::SchemaError::Malformed {
-                        path: ctx.fragment.join("/"),
-                        detail: "String values MUST be one of the seven primitive types defined by the core specification.".to_string()
-                    })
+                    return Err(ctx.malformed("String values MUST be one of the seven primitive types defined by the core specification.".to_string()))
                 }
             }
 
             Ok(Some(Box::new(validators::Type {
                 fragment: ctx.fragment.clone(),
+                keyword: "type",
                 item: validators::type_::TypeKind::Set(converted_types)
             })))
         } else {
-            Err(schema::SchemaError::Malformed {
-                path: ctx.fragment.join("/"),
-                detail: "The value of this keyword MUST be either a string or an array.".to_string()
-            })
+            Err(ctx.malformed("The value of this keyword MUST be either a string or an array.".to_string()))
         }
     }
 }
@@ -0,0 +1,185 @@
+use serde_json::{Value};
+
+use super::super::schema;
+use super::super::validators;
+use super::super::helpers;
+
+#[allow(missing_copy_implementations)]
+pub struct UnevaluatedProperties;
+impl super::Keyword for UnevaluatedProperties {
+    fn compile(&self, def: &Value, ctx: &schema::WalkContext) -> super::KeywordResult {
+        let unevaluated = keyword_key_exists!(def, "unevaluatedProperties");
+
+        let kind = if unevaluated.is_boolean() {
+            validators::properties::AdditionalKind::Boolean(unevaluated.as_bool().unwrap())
+        } else if unevaluated.is_object() {
+            validators::properties::AdditionalKind::Schema(
+                ctx.link(helpers::alter_fragment_path(ctx.url.clone(), [
+                    ctx.escaped_fragment().as_ref(),
+                    "unevaluatedProperties"
+                ].join("/")))
+            )
+        } else {
+            return Err(ctx.malformed("The value of `unevaluatedProperties` MUST be a valid JSON Schema".to_string()))
+        };
+
+        Ok(Some(Box::new(validators::UnevaluatedProperties {
+            fragment: ctx.fragment.clone(),
+            keyword: "unevaluatedProperties",
+            unevaluated: kind
+        })))
+    }
+}
+
+#[allow(missing_copy_implementations)]
+pub struct UnevaluatedItems;
+impl super::Keyword for UnevaluatedItems {
+    fn compile(&self, def: &Value, ctx: &schema::WalkContext) -> super::KeywordResult {
+        let unevaluated = keyword_key_exists!(def, "unevaluatedItems");
+
+        let kind = if unevaluated.is_boolean() {
+            validators::items::AdditionalKind::Boolean(unevaluated.as_bool().unwrap())
+        } else if unevaluated.is_object() {
+            validators::items::AdditionalKind::Schema(
+                ctx.link(helpers::alter_fragment_path(ctx.url.clone(), [
+                    ctx.escaped_fragment().as_ref(),
+                    "unevaluatedItems"
+                ].join("/")))
+            )
+        } else {
+            return Err(ctx.malformed("The value of `unevaluatedItems` MUST be a valid JSON Schema".to_string()))
+        };
+
+        Ok(Some(Box::new(validators::UnevaluatedItems {
+            fragment: ctx.fragment.clone(),
+            keyword: "unevaluatedItems",
+            unevaluated: kind
+        })))
+    }
+}
+
+#[cfg(test)] use super::super::scope;
+#[cfg(test)] use jsonway;
+#[cfg(test)] use super::super::builder;
+#[cfg(test)] use serde_json::to_value;
+
+#[test]
+fn validate_unevaluated_properties() {
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(jsonway::object(|schema| {
+        schema.set("$schema", "https://json-schema.org/draft/2019-09/schema".to_string());
+        schema.set("properties", jsonway::object(|props| {
+            props.set("foo", builder::schema(|foo| { foo.string(); }).into_json());
+        }).unwrap());
+        schema.array("allOf", |all_of| {
+            all_of.push(jsonway::object(|sub| {
+                sub.set("properties", jsonway::object(|props| {
+                    props.set("bar", builder::schema(|bar| { bar.string(); }).into_json());
+                }).unwrap());
+            }).unwrap());
+        });
+        schema.set("unevaluatedProperties", false);
+    }).unwrap(), true).ok().unwrap();
+
+    assert_eq!(schema.validate(&jsonway::object(|obj| {
+        obj.set("foo", "foo".to_string());
+        obj.set("bar", "bar".to_string());
+    }).unwrap()).is_valid(), true);
+
+    assert_eq!(schema.validate(&jsonway::object(|obj| {
+        obj.set("foo", "foo".to_string());
+        obj.set("baz", "baz".to_string());
+    }).unwrap()).is_valid(), false);
+}
+
+#[test]
+fn validate_unevaluated_properties_of_nested_objects() {
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(jsonway::object(|schema| {
+        schema.set("$schema", "https://json-schema.org/draft/2019-09/schema".to_string());
+        schema.set("properties", jsonway::object(|props| {
+            props.set("a", jsonway::object(|a| {
+                a.set("properties", jsonway::object(|props| {
+                    props.set("b", jsonway::object(|_| {}).unwrap());
+                }).unwrap());
+            }).unwrap());
+        }).unwrap());
+        schema.set("unevaluatedProperties", false);
+    }).unwrap(), true).ok().unwrap();
+
+    assert_eq!(schema.validate(&jsonway::object(|obj| {
+        obj.object("a", |a| { a.set("b", 1); });
+    }).unwrap()).is_valid(), true);
+
+    // `b` is evaluated in `a`, which doesn't make the `a/b` property evaluated
+    assert_eq!(schema.validate(&jsonway::object(|obj| {
+        obj.object("a", |a| { a.set("b", 1); });
+        obj.set("a/b", 2);
+    }).unwrap()).is_valid(), false);
+}
+
+#[test]
+fn validate_unevaluated_properties_through_any_of() {
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(jsonway::object(|schema| {
+        schema.set("$schema", "https://json-schema.org/draft/2020-12/schema".to_string());
+        schema.array("anyOf", |any_of| {
+            any_of.push(jsonway::object(|sub| {
+                sub.set("properties", jsonway::object(|props| {
+                    props.set("foo", builder::schema(|foo| { foo.const_("a"); }).into_json());
+                }).unwrap());
+                sub.array("required", |req| req.push("foo".to_string()));
+            }).unwrap());
+            any_of.push(jsonway::object(|sub| {
+                sub.set("properties", jsonway::object(|props| {
+                    props.set("bar", builder::schema(|bar| { bar.const_("b"); }).into_json());
+                }).unwrap());
+                sub.array("required", |req| req.push("bar".to_string()));
+            }).unwrap());
+        });
+        schema.set("unevaluatedProperties", builder::schema(|s| { s.number(); }).into_json());
+    }).unwrap(), true).ok().unwrap();
+
+    assert_eq!(schema.validate(&jsonway::object(|obj| {
+        obj.set("foo", "a".to_string());
+        obj.set("bar", "b".to_string());
+    }).unwrap()).is_valid(), true);
+
+    // `bar` is evaluated only by the failed branch
+    assert_eq!(schema.validate(&jsonway::object(|obj| {
+        obj.set("foo", "a".to_string());
+        obj.set("bar", "c".to_string());
+    }).unwrap()).is_valid(), false);
+
+    assert_eq!(schema.validate(&jsonway::object(|obj| {
+        obj.set("foo", "a".to_string());
+        obj.set("baz", 1);
+    }).unwrap()).is_valid(), true);
+}
+
+#[test]
+fn validate_unevaluated_items() {
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(jsonway::object(|schema| {
+        schema.set("$schema", "https://json-schema.org/draft/2020-12/schema".to_string());
+        schema.array("prefixItems", |items| {
+            items.push(builder::schema(|s| { s.string(); }).into_json());
+        });
+        schema.set("contains", builder::schema(|s| { s.number(); }).into_json());
+        schema.set("unevaluatedItems", false);
+    }).unwrap(), true).ok().unwrap();
+
+    assert_eq!(schema.validate(&jsonway::array(|arr| {
+        arr.push("foo".to_string());
+        arr.push(1);
+        arr.push(2);
+    }).unwrap()).is_valid(), true);
+
+    assert_eq!(schema.validate(&jsonway::array(|arr| {
+        arr.push("foo".to_string());
+        arr.push(1);
+        arr.push(true);
+    }).unwrap()).is_valid(), false);
+
+    assert_eq!(schema.validate(&to_value("not an array").unwrap()).is_valid(), true);
+}
@@ -13,15 +13,13 @@ impl super::Keyword for UniqueItems {
             if uniq.as_bool().unwrap() {
                 Ok(Some(Box::new(validators::UniqueItems {
                     fragment: ctx.fragment.clone(),
+                    keyword: "uniqueItems",
                 })))
             } else {
                 Ok(None)
             }
         } else {
-            Err(schema::SchemaError::Malformed {
-                path: ctx.fragment.join("/"),
-                detail: "The value of pattern MUST be boolean".to_string()
-            })
+            Err(ctx.malformed("The value of pattern MUST be boolean".to_string()))
         }
     }
 }
@@ -0,0 +1,316 @@
+use std::collections;
+use std::fs;
+use std::fmt;
+use std::path;
+use serde_json::{self, Value};
+use url;
+
+use super::schema;
+
+/// Source of schema documents that are referenced but not added to the `Scope`.
+///
+/// The loader is asked for the document part of the reference (the URL without
+/// fragment). It returns `Ok(None)` when it doesn't know the document, so the next
+/// loader can be asked, and `Err` when the document exists but can't be loaded.
+pub trait Loader: Send + Sync {
+    fn load(&self, url: &url::Url) -> Result<Option<Value>, String>;
+}
+
+impl fmt::Debug for Loader + 'static {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str("<loader>")
+    }
+}
+
+impl<T> Loader for T where T: Fn(&url::Url) -> Result<Option<Value>, String> + Send + Sync {
+    fn load(&self, url: &url::Url) -> Result<Option<Value>, String> {
+        self(url)
+    }
+}
+
+/// Loads `file://` documents from the local filesystem.
+#[allow(missing_copy_implementations)]
+pub struct FileLoader;
+
+impl Loader for FileLoader {
+    fn load(&self, url: &url::Url) -> Result<Option<Value>, String> {
+        if url.scheme() != "file" {
+            return Ok(None)
+        }
+
+        let path = try!(url.to_file_path().map_err(|_| "The URL is not a valid file path".to_string()));
+        read_file(&path).map(Some)
+    }
+}
+
+pub fn read_file(path: &path::Path) -> Result<Value, String> {
+    let file = try!(fs::File::open(path).map_err(|err| err.to_string()));
+    serde_json::from_reader(file).map_err(|err| err.to_string())
+}
+
+/// A schema file of a directory that failed to load or to compile.
+#[derive(Debug)]
+pub struct FileError {
+    pub path: path::PathBuf,
+    pub error: schema::SchemaError
+}
+
+/// Lists the `.json` files under the directory along with the URLs their relative
+/// paths map to under `base`. Directories that can't be read are reported in `errors`.
+pub fn list_dir(dir: &path::Path, base: &url::Url, errors: &mut Vec<FileError>) -> Vec<(path::PathBuf, url::Url)> {
+    let mut base = base.clone();
+    if !base.path().ends_with("/") {
+        let path = base.path().to_string() + "/";
+        base.set_path(&path);
+    }
+
+    let mut files = vec![];
+    walk_dir(dir, &base, &mut files, errors);
+    files
+}
+
+fn walk_dir(dir: &path::Path, base: &url::Url, files: &mut Vec<(path::PathBuf, url::Url)>, errors: &mut Vec<FileError>) {
+    let entries = fs::read_dir(dir).and_then(|entries| entries.collect::<Result<Vec<fs::DirEntry>, _>>());
+    let mut entries = match entries {
+        Ok(entries) => entries,
+        Err(err) => {
+            errors.push(FileError {
+                path: dir.to_path_buf(),
+                error: schema::SchemaError::LoadError { url: base.clone(), pointer: "".to_string(), detail: err.to_string() }
+            });
+            return
+        }
+    };
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().into_owned();
+
+        if path.is_dir() {
+            match base.join(&(name + "/")) {
+                Ok(base) => walk_dir(&path, &base, files, errors),
+                Err(err) => errors.push(FileError {
+                    path: path,
+                    error: schema::SchemaError::UrlParseError { url: base.clone(), pointer: "".to_string(), error: err }
+                })
+            }
+        } else if path.extension().map(|ext| ext == "json").unwrap_or(false) {
+            match base.join(&name) {
+                Ok(url) => files.push((path, url)),
+                Err(err) => errors.push(FileError {
+                    path: path,
+                    error: schema::SchemaError::UrlParseError { url: base.clone(), pointer: "".to_string(), error: err }
+                })
+            }
+        }
+    }
+}
+
+/// Serves documents kept in memory, keyed by their URL.
+#[derive(Debug)]
+pub struct MemoryLoader {
+    schemas: collections::HashMap<String, Value>
+}
+
+impl MemoryLoader {
+    pub fn new() -> MemoryLoader {
+        MemoryLoader {
+            schemas: collections::HashMap::new()
+        }
+    }
+
+    pub fn insert(&mut self, url: &url::Url, schema: Value) {
+        let mut url = url.clone();
+        url.set_fragment(None);
+        self.schemas.insert(url.into_string(), schema);
+    }
+}
+
+impl Loader for MemoryLoader {
+    fn load(&self, url: &url::Url) -> Result<Option<Value>, String> {
+        Ok(self.schemas.get(url.as_str()).cloned())
+    }
+}
+
+#[cfg(test)] use super::scope;
+#[cfg(test)] use jsonway;
+#[cfg(test)] use serde_json::to_value;
+#[cfg(test)] use std::env;
+#[cfg(test)] use std::io::Write;
+#[cfg(test)] use std::process;
+
+#[test]
+fn load_transitive_refs() {
+    let mut loader = MemoryLoader::new();
+    loader.insert(&url::Url::parse("http://example.com/a.json").unwrap(), jsonway::object(|schema| {
+        schema.set("$ref", "b.json#/definitions/positive".to_string());
+    }).unwrap());
+    loader.insert(&url::Url::parse("http://example.com/b.json").unwrap(), jsonway::object(|schema| {
+        schema.object("definitions", |defs| {
+            defs.object("positive", |positive| {
+                positive.set("minimum", 0);
+            });
+        });
+    }).unwrap());
+
+    let mut scope = scope::Scope::new();
+    scope.add_loader(loader);
+
+    let schema = scope.compile_and_return(jsonway::object(|schema| {
+        schema.set("$ref", "http://example.com/a.json".to_string());
+    }).unwrap(), true).ok().unwrap();
+
+    assert!(schema.validate(&to_value(1).unwrap()).is_strictly_valid());
+    assert!(!schema.validate(&to_value(-1).unwrap()).is_valid());
+}
+
+#[test]
+fn load_cyclic_refs() {
+    let mut scope = scope::Scope::new();
+    scope.add_loader(|url: &url::Url| {
+        match url.as_str() {
+            "http://example.com/node.json" => Ok(Some(jsonway::object(|schema| {
+                schema.set("type", "object".to_string());
+                schema.object("properties", |props| {
+                    props.object("next", |next| {
+                        next.set("$ref", "list.json".to_string());
+                    });
+                });
+            }).unwrap())),
+            "http://example.com/list.json" => Ok(Some(jsonway::object(|schema| {
+                schema.set("$ref", "node.json".to_string());
+            }).unwrap())),
+            _ => Ok(None)
+        }
+    });
+
+    let schema = scope.compile_and_return(jsonway::object(|schema| {
+        schema.set("$ref", "http://example.com/list.json".to_string());
+    }).unwrap(), true).ok().unwrap();
+
+    assert!(schema.validate(&jsonway::object(|obj| {
+        obj.object("next", |next| {
+            next.object("next", |_| {});
+        });
+    }).unwrap()).is_strictly_valid());
+
+    assert!(!schema.validate(&jsonway::object(|obj| {
+        obj.set("next", 1);
+    }).unwrap()).is_valid());
+}
+
+#[test]
+fn load_error() {
+    let mut scope = scope::Scope::new();
+    scope.add_loader(|_: &url::Url| Err("Connection refused".to_string()));
+
+    let result = scope.compile(jsonway::object(|schema| {
+        schema.set("id", "http://example.com/root.json".to_string());
+        schema.set("$ref", "http://example.com/remote.json".to_string());
+    }).unwrap(), true);
+
+    match result {
+        Err(schema::SchemaError::LoadError { url, detail, .. }) => {
+            assert_eq!(url.as_str(), "http://example.com/remote.json");
+            assert_eq!(detail, "Connection refused");
+        },
+        _ => panic!("Expected a load error")
+    }
+
+    // Nothing is left in the scope after a failed compilation
+    assert!(scope.resolve(&url::Url::parse("http://example.com/root.json").unwrap()).is_none());
+}
+
+// Tests run in parallel, also across processes, so each one gets its own path
+#[cfg(test)]
+fn temp_path(name: &str) -> path::PathBuf {
+    env::temp_dir().join(format!("valico_{}_{}", process::id(), name))
+}
+
+#[test]
+fn load_file() {
+    let path = temp_path("loader_test.json");
+    fs::File::create(&path).unwrap().write_all(b"{\"type\": \"string\"}").unwrap();
+
+    let mut scope = scope::Scope::new();
+    scope.add_loader(FileLoader);
+
+    let schema = scope.compile_and_return(jsonway::object(|schema| {
+        schema.set("$ref", url::Url::from_file_path(&path).unwrap().into_string());
+    }).unwrap(), true).ok().unwrap();
+
+    assert!(schema.validate(&to_value("string").unwrap()).is_strictly_valid());
+    assert!(!schema.validate(&to_value(1).unwrap()).is_valid());
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[cfg(test)]
+fn write_file(path: &path::Path, content: &str) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::File::create(path).unwrap().write_all(content.as_bytes()).unwrap();
+}
+
+#[test]
+fn compile_dir() {
+    let dir = temp_path("compile_dir_test");
+    let _ = fs::remove_dir_all(&dir);
+    write_file(&dir.join("user.json"), r#"{"type": "object", "properties": {"address": {"$ref": "common/address.json"}}}"#);
+    write_file(&dir.join("common/address.json"), r#"{"type": "object", "required": ["city"]}"#);
+    write_file(&dir.join("README.md"), "Not a schema");
+
+    let mut scope = scope::Scope::new();
+    let ids = scope.compile_dir(&dir, &url::Url::parse("https://schemas.example.com").unwrap(), true).ok().unwrap();
+
+    assert_eq!(ids.iter().map(|id| id.as_str()).collect::<Vec<&str>>(), vec![
+        "https://schemas.example.com/common/address.json",
+        "https://schemas.example.com/user.json"
+    ]);
+
+    let schema = scope.resolve(&url::Url::parse("https://schemas.example.com/user.json").unwrap()).unwrap();
+    assert!(schema.validate(&jsonway::object(|obj| {
+        obj.object("address", |address| address.set("city", "Berlin".to_string()));
+    }).unwrap()).is_strictly_valid());
+    assert!(!schema.validate(&jsonway::object(|obj| {
+        obj.object("address", |_| {});
+    }).unwrap()).is_valid());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn compile_dir_errors() {
+    let dir = temp_path("compile_dir_errors_test");
+    let _ = fs::remove_dir_all(&dir);
+    write_file(&dir.join("valid.json"), r#"{"type": "string"}"#);
+    write_file(&dir.join("broken.json"), r#"{"type": "#);
+    write_file(&dir.join("nested/malformed.json"), r#"{"minimum": "5"}"#);
+    write_file(&dir.join("dangling.json"), r#"{"$ref": "missing.json"}"#);
+
+    let mut scope = scope::Scope::new();
+    let errors = scope.compile_dir(&dir, &url::Url::parse("https://schemas.example.com/").unwrap(), true).err().unwrap();
+
+    assert_eq!(errors.len(), 3);
+    assert_eq!(errors[0].path, dir.join("broken.json"));
+    match errors[0].error {
+        schema::SchemaError::LoadError { .. } => (),
+        _ => panic!("Expected a load error")
+    }
+    assert_eq!(errors[1].path, dir.join("nested/malformed.json"));
+    match errors[1].error {
+        schema::SchemaError::Malformed { .. } => (),
+        _ => panic!("Expected a malformed schema error")
+    }
+    // The references are still linked when other files are broken
+    assert_eq!(errors[2].path, dir.join("dangling.json"));
+    match errors[2].error {
+        schema::SchemaError::UnresolvedRef { .. } => (),
+        _ => panic!("Expected an unresolved reference error")
+    }
+
+    assert!(scope.resolve(&url::Url::parse("https://schemas.example.com/valid.json").unwrap()).is_none());
+    assert!(scope.resolve(&url::Url::parse("https://schemas.example.com/dangling.json").unwrap()).is_none());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
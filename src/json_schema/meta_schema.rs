@@ -0,0 +1,84 @@
+//! The meta-schemas of the supported drafts, used to check the schemas before
+//! compiling them.
+
+use url;
+use serde_json;
+
+use super::scope;
+use super::Draft;
+
+/// The documents of the meta-schemas, with their ids. The vocabularies of the
+/// later drafts come before the meta-schemas that refer to them.
+const DOCUMENTS: [(&'static str, &'static str); 18] = [
+    ("http://json-schema.org/draft-04/schema", include_str!("../../tests/schema/schema.json")),
+    ("http://json-schema.org/draft-06/schema", include_str!("meta_schemas/draft-06.json")),
+    ("http://json-schema.org/draft-07/schema", include_str!("meta_schemas/draft-07.json")),
+
+    ("https://json-schema.org/draft/2019-09/meta/core", include_str!("meta_schemas/draft2019-09/meta/core.json")),
+    ("https://json-schema.org/draft/2019-09/meta/applicator", include_str!("meta_schemas/draft2019-09/meta/applicator.json")),
+    ("https://json-schema.org/draft/2019-09/meta/validation", include_str!("meta_schemas/draft2019-09/meta/validation.json")),
+    ("https://json-schema.org/draft/2019-09/meta/meta-data", include_str!("meta_schemas/draft2019-09/meta/meta-data.json")),
+    ("https://json-schema.org/draft/2019-09/meta/format", include_str!("meta_schemas/draft2019-09/meta/format.json")),
+    ("https://json-schema.org/draft/2019-09/meta/content", include_str!("meta_schemas/draft2019-09/meta/content.json")),
+    ("https://json-schema.org/draft/2019-09/schema", include_str!("meta_schemas/draft2019-09/schema.json")),
+
+    ("https://json-schema.org/draft/2020-12/meta/core", include_str!("meta_schemas/draft2020-12/meta/core.json")),
+    ("https://json-schema.org/draft/2020-12/meta/applicator", include_str!("meta_schemas/draft2020-12/meta/applicator.json")),
+    ("https://json-schema.org/draft/2020-12/meta/unevaluated", include_str!("meta_schemas/draft2020-12/meta/unevaluated.json")),
+    ("https://json-schema.org/draft/2020-12/meta/validation", include_str!("meta_schemas/draft2020-12/meta/validation.json")),
+    ("https://json-schema.org/draft/2020-12/meta/meta-data", include_str!("meta_schemas/draft2020-12/meta/meta-data.json")),
+    ("https://json-schema.org/draft/2020-12/meta/format-annotation", include_str!("meta_schemas/draft2020-12/meta/format-annotation.json")),
+    ("https://json-schema.org/draft/2020-12/meta/content", include_str!("meta_schemas/draft2020-12/meta/content.json")),
+    ("https://json-schema.org/draft/2020-12/schema", include_str!("meta_schemas/draft2020-12/schema.json")),
+];
+
+/// The id of the meta-schema of the draft.
+pub fn url(draft: Draft) -> url::Url {
+    let url = match draft {
+        Draft::V4 => DOCUMENTS[0].0,
+        Draft::V6 => DOCUMENTS[1].0,
+        Draft::V7 => DOCUMENTS[2].0,
+        Draft::V2019_09 => DOCUMENTS[9].0,
+        Draft::V2020_12 => DOCUMENTS[17].0
+    };
+
+    url::Url::parse(url).unwrap()
+}
+
+/// A scope with the meta-schemas of every supported draft. Formats are only
+/// annotations in the meta-schemas, so they aren't checked.
+pub fn scope() -> scope::Scope {
+    let mut scope = scope::Scope::without_formats();
+    for &(id, source) in DOCUMENTS.iter() {
+        let def = serde_json::from_str(source).unwrap();
+        scope.compile_with_id(&url::Url::parse(id).unwrap(), def, false).ok().unwrap();
+    }
+
+    scope
+}
+
+/// Serves the bundled meta-schemas and vocabularies, so that schemas compiled
+/// with `scope.add_loader(meta_schema::load)` can refer to them.
+pub fn load(url: &url::Url) -> Result<Option<serde_json::Value>, String> {
+    let mut url = url.clone();
+    url.set_fragment(None);
+
+    match DOCUMENTS.iter().find(|&&(id, _)| id == url.as_str()) {
+        Some(&(_, source)) => serde_json::from_str(source).map(Some).map_err(|err| err.to_string()),
+        None => Ok(None)
+    }
+}
+
+#[cfg(test)] use serde_json::from_str;
+
+#[test]
+fn load_meta_schemas() {
+    let mut scope = scope::Scope::new();
+    scope.add_loader(load);
+
+    let schema = scope.compile_and_return(from_str(r#"{"$ref": "http://json-schema.org/draft-07/schema#"}"#).unwrap(), false).ok().unwrap();
+    assert!(schema.validate(&from_str(r#"{"type": "string"}"#).unwrap()).is_valid());
+    assert!(!schema.validate(&from_str(r#"{"type": 1}"#).unwrap()).is_valid());
+
+    assert_eq!(load(&url::Url::parse("http://example.com/schema").unwrap()), Ok(None));
+}
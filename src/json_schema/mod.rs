@@ -1,5 +1,6 @@
 use std::str;
 use std::fmt;
+use serde_json::{Value};
 
 #[macro_use] pub mod helpers;
 #[macro_use] pub mod keywords;
@@ -8,11 +9,20 @@ pub mod scope;
 pub mod validators;
 pub mod errors;
 pub mod builder;
+pub mod loader;
+pub mod output;
+pub mod error_message;
+pub mod report;
+pub mod meta_schema;
+pub mod ecma_regex;
 
 pub use self::scope::{Scope};
-pub use self::schema::{Schema, SchemaError};
+pub use self::schema::{Schema, SchemaError, CompiledSchema, Limits};
 pub use self::builder::{Builder, schema};
-pub use self::validators::{ValidationState};
+pub use self::loader::{Loader, FileLoader, MemoryLoader, FileError};
+pub use self::validators::{ValidationState, DynamicScope, Annotation};
+pub use self::output::{OutputFormat};
+pub use self::keywords::format::{FormatMode};
 
 #[derive(Copy, Debug, Clone)]
 pub enum PrimitiveType {
@@ -53,4 +63,58 @@ impl fmt::Display for PrimitiveType {
             &PrimitiveType::String => "string",
         })
     }
-}
\ No newline at end of file
+}
+
+/// JSON Schema dialect a schema is compiled with.
+///
+/// The dialect is picked from the `$schema` keyword of the schema document
+/// and falls back to the default dialect of the `Scope` (Draft 4 unless
+/// configured otherwise).
+#[derive(Copy, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Draft {
+    V4,
+    V6,
+    V7,
+    V2019_09,
+    V2020_12,
+}
+
+impl Draft {
+    /// Detects the dialect by the meta-schema URI used in `$schema`.
+    pub fn from_url(url: &str) -> Option<Draft> {
+        let url = url.trim_end_matches('#')
+                     .trim_start_matches("https://")
+                     .trim_start_matches("http://");
+
+        match url {
+            "json-schema.org/draft-04/schema" => Some(Draft::V4),
+            "json-schema.org/draft-06/schema" => Some(Draft::V6),
+            "json-schema.org/draft-07/schema" => Some(Draft::V7),
+            "json-schema.org/draft/2019-09/schema" => Some(Draft::V2019_09),
+            "json-schema.org/draft/2020-12/schema" => Some(Draft::V2020_12),
+            _ => None
+        }
+    }
+
+    pub fn from_schema(def: &Value) -> Option<Draft> {
+        def.get("$schema").and_then(|schema| schema.as_str()).and_then(Draft::from_url)
+    }
+
+    /// The keyword that holds the schema identifier.
+    pub fn id_key(&self) -> &'static str {
+        match self {
+            &Draft::V4 => "id",
+            _ => "$id"
+        }
+    }
+
+    /// Whether `true` and `false` are accepted as schemas.
+    pub fn allows_boolean_schemas(&self) -> bool {
+        *self >= Draft::V6
+    }
+
+    /// Whether plain-name fragments can be declared with `$anchor`.
+    pub fn allows_anchors(&self) -> bool {
+        *self >= Draft::V2019_09
+    }
+}
@@ -0,0 +1,252 @@
+//! Standard output formats of JSON Schema 2019-09 rendered from a `ValidationState`.
+
+use serde_json::{Value, to_value};
+
+use super::super::common::error::{self, ValicoError};
+use super::errors;
+use super::validators;
+
+#[derive(Copy, Debug, Clone, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Only whether the instance is valid.
+    Flag,
+    /// A flat list of every error.
+    Basic,
+    /// Errors nested under the `anyOf`, `oneOf` and `propertyNames` errors that caused
+    /// them.
+    Detailed,
+    /// Every schema applied to the instance, valid or not, in the hierarchy of the
+    /// schema. It needs the state of `ScopedSchema::validate_verbose`, other states
+    /// only have the failed subschemas of combinators beside the `Detailed` units.
+    Verbose
+}
+
+pub fn render(state: &validators::ValidationState, format: OutputFormat) -> Value {
+    let mut map = ::serde_json::Map::new();
+    map.insert("valid".to_string(), Value::Bool(state.is_valid()));
+
+    match format {
+        OutputFormat::Flag => (),
+        OutputFormat::Basic => {
+            if !state.is_valid() {
+                let mut units = vec![];
+                flatten(state, &mut units);
+                map.insert("errors".to_string(), Value::Array(units));
+            }
+        },
+        OutputFormat::Verbose if state.evaluations.len() == 1 => {
+            let mut errors = vec![];
+            collect_errors(state, &mut errors);
+            return Value::Object(evaluation_unit(&state.evaluations[0], &errors))
+        },
+        OutputFormat::Detailed | OutputFormat::Verbose => {
+            map.insert("keywordLocation".to_string(), to_value("").unwrap());
+            map.insert("instanceLocation".to_string(), to_value("").unwrap());
+            if !state.is_valid() {
+                map.insert("errors".to_string(), Value::Array(nest(state, format == OutputFormat::Verbose)));
+            }
+        }
+    }
+
+    Value::Object(map)
+}
+
+fn unit(error: &ValicoError) -> ::serde_json::Map<String, Value> {
+    let mut map = ::serde_json::Map::new();
+    map.insert("valid".to_string(), Value::Bool(false));
+    let location = error.get_keyword_location().unwrap_or(error.get_fragment());
+    map.insert("keywordLocation".to_string(), to_value(error::to_pointer(location)).unwrap());
+    match error.get_absolute_keyword_location() {
+        Some(location) => { map.insert("absoluteKeywordLocation".to_string(), to_value(location).unwrap()); },
+        None => ()
+    }
+    map.insert("instanceLocation".to_string(), to_value(error.get_path()).unwrap());
+    map.insert("error".to_string(), to_value(error.get_detail().unwrap_or(error.get_title())).unwrap());
+    map
+}
+
+fn flatten(state: &validators::ValidationState, units: &mut Vec<Value>) {
+    for error in state.errors.iter() {
+        units.push(Value::Object(unit(&**error)));
+        for nested in errors::nested_states(&**error).iter() {
+            flatten(nested, units);
+        }
+    }
+}
+
+fn nest(state: &validators::ValidationState, verbose: bool) -> Vec<Value> {
+    state.errors.iter().map(|error| {
+        let mut map = unit(&**error);
+        let nested = errors::nested_states(&**error);
+
+        if nested.len() > 0 {
+            let children = if verbose {
+                nested.iter().map(|nested| {
+                    let mut branch = unit(&**error);
+                    branch.remove("error");
+                    branch.insert("errors".to_string(), Value::Array(nest(nested, verbose)));
+                    Value::Object(branch)
+                }).collect()
+            } else {
+                nested.iter().flat_map(|nested| nest(nested, verbose)).collect()
+            };
+            map.insert("errors".to_string(), Value::Array(children));
+        }
+
+        Value::Object(map)
+    }).collect()
+}
+
+fn collect_errors<'a>(state: &'a validators::ValidationState, errors: &mut Vec<&'a ValicoError>) {
+    for error in state.errors.iter() {
+        errors.push(&**error);
+        for nested in errors::nested_states(&**error).iter() {
+            collect_errors(nested, errors);
+        }
+    }
+}
+
+fn evaluation_unit(evaluation: &validators::Evaluation, errors: &[&ValicoError]) -> ::serde_json::Map<String, Value> {
+    let mut map = ::serde_json::Map::new();
+    map.insert("valid".to_string(), Value::Bool(evaluation.valid));
+    map.insert("keywordLocation".to_string(), to_value(error::to_pointer(&evaluation.location.path)).unwrap());
+    match evaluation.location.absolute {
+        Some(ref location) => { map.insert("absoluteKeywordLocation".to_string(), to_value(location).unwrap()); },
+        None => ()
+    }
+    map.insert("instanceLocation".to_string(), to_value(&evaluation.path).unwrap());
+
+    let mut children = errors.iter().filter(|error| is_raised_by(**error, evaluation)).map(|error| {
+        Value::Object(unit(*error))
+    }).collect::<Vec<Value>>();
+    children.extend(evaluation.evaluations.iter().map(|child| Value::Object(evaluation_unit(child, errors))));
+
+    if children.len() > 0 {
+        map.insert((if evaluation.valid { "annotations" } else { "errors" }).to_string(), Value::Array(children));
+    }
+
+    map
+}
+
+/// Whether the error comes from a keyword of the evaluated schema itself rather than
+/// from one of its subschemas.
+fn is_raised_by(error: &ValicoError, evaluation: &validators::Evaluation) -> bool {
+    let location = match error.get_keyword_location() {
+        Some(location) => location,
+        None => return false
+    };
+    // The error of the `false` schema is located at the schema, not at a keyword
+    let schema_location = if error.is::<errors::BooleanSchema>() {
+        location
+    } else {
+        &location[..location.len().saturating_sub(1)]
+    };
+
+    let path = error.get_path();
+    schema_location == &evaluation.location.path[..] &&
+        (path == evaluation.path || path.starts_with(&(evaluation.path.clone() + "/")))
+}
+
+#[cfg(test)] use super::scope;
+#[cfg(test)] use jsonway;
+
+#[cfg(test)]
+fn invalid_state() -> validators::ValidationState {
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(jsonway::object(|schema| {
+        schema.object("properties", |props| {
+            props.object("a/b", |a| a.set("maximum", 10));
+            props.object("c", |c| {
+                c.array("anyOf", |any_of| {
+                    any_of.object(|s| s.set("type", "string".to_string()));
+                    any_of.object(|s| {
+                        s.set("type", "integer".to_string());
+                        s.set("minimum", 5);
+                    });
+                });
+            });
+        });
+    }).unwrap(), true).ok().unwrap();
+
+    schema.validate(&jsonway::object(|obj| {
+        obj.set("a/b", 12);
+        obj.set("c", 1);
+    }).unwrap())
+}
+
+#[test]
+fn render_flag() {
+    assert_eq!(render(&invalid_state(), OutputFormat::Flag), jsonway::object(|obj| obj.set("valid", false)).unwrap());
+    assert_eq!(render(&validators::ValidationState::new(), OutputFormat::Basic), jsonway::object(|obj| obj.set("valid", true)).unwrap());
+}
+
+#[test]
+fn render_basic() {
+    let output = render(&invalid_state(), OutputFormat::Basic);
+    let mut locations = output["errors"].as_array().unwrap().iter().map(|unit| {
+        (unit["keywordLocation"].as_str().unwrap(), unit["instanceLocation"].as_str().unwrap())
+    }).collect::<Vec<(&str, &str)>>();
+    locations.sort();
+
+    assert_eq!(locations, vec![
+        ("/properties/a~1b/maximum", "/a~1b"),
+        ("/properties/c/anyOf", "/c"),
+        ("/properties/c/anyOf/0/type", "/c"),
+        ("/properties/c/anyOf/1/minimum", "/c")
+    ]);
+    assert!(output["errors"][0]["error"].is_string());
+
+    // The fragment of an error stays the location of its schema
+    let state = invalid_state();
+    let maximum = state.errors.iter().find(|error| error.get_code() == "maximum").unwrap();
+    assert_eq!(maximum.get_fragment(), &["properties".to_string(), "a/b".to_string()]);
+}
+
+#[test]
+fn render_detailed_and_verbose() {
+    let state = invalid_state();
+
+    let detailed = render(&state, OutputFormat::Detailed);
+    let any_of = detailed["errors"].as_array().unwrap().iter()
+        .find(|unit| unit["keywordLocation"] == "/properties/c/anyOf").unwrap();
+    assert_eq!(any_of["errors"].as_array().unwrap().len(), 2);
+    assert_eq!(any_of["errors"][0]["valid"], false);
+
+    let verbose = render(&state, OutputFormat::Verbose);
+    let any_of = verbose["errors"].as_array().unwrap().iter()
+        .find(|unit| unit["keywordLocation"] == "/properties/c/anyOf").unwrap();
+    let branches = any_of["errors"].as_array().unwrap();
+    assert_eq!(branches.len(), 2);
+    assert!(branches.iter().all(|branch| branch["errors"].as_array().unwrap().len() == 1));
+}
+
+#[test]
+fn render_verbose_hierarchy() {
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(jsonway::object(|schema| {
+        schema.object("properties", |props| {
+            props.object("a", |a| a.set("maximum", 10));
+            props.object("b", |b| b.set("type", "string".to_string()));
+        });
+    }).unwrap(), true).ok().unwrap();
+
+    let output = render(&schema.validate_verbose(&jsonway::object(|obj| {
+        obj.set("a", 12);
+        obj.set("b", "b".to_string());
+    }).unwrap()), OutputFormat::Verbose);
+
+    assert_eq!(output["valid"], false);
+    assert_eq!(output["keywordLocation"], "");
+    let units = output["errors"].as_array().unwrap();
+    assert_eq!(units.len(), 2);
+
+    // The passing subschema is part of the output
+    let b = units.iter().find(|unit| unit["keywordLocation"] == "/properties/b").unwrap();
+    assert_eq!(b["valid"], true);
+    assert_eq!(b["instanceLocation"], "/b");
+
+    let a = units.iter().find(|unit| unit["keywordLocation"] == "/properties/a").unwrap();
+    assert_eq!(a["valid"], false);
+    assert_eq!(a["errors"][0]["keywordLocation"], "/properties/a/maximum");
+    assert_eq!(a["errors"][0]["instanceLocation"], "/a");
+}
@@ -0,0 +1,351 @@
+//! Compiler-like reports of the errors of a `ValidationState`, pointing at the
+//! offending values in the original JSON text.
+
+use super::super::common::error::{self, ValicoError};
+use super::validators;
+
+macro_rules! try_opt {
+    ($e:expr) => (match $e { Some(value) => value, None => return None })
+}
+
+#[derive(Copy, Debug, Clone, PartialEq, Eq)]
+pub enum Style {
+    Plain,
+    /// Colored with ANSI escape codes, for terminals.
+    Ansi
+}
+
+/// Location of a value in the JSON text. Lines and columns start at 1.
+#[derive(Copy, Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize
+}
+
+/// Finds the value at the instance path (like `/items/0/name`) in the JSON text.
+/// The path is a JSON pointer, with `~` and `/` escaped in the keys. Paths that
+/// don't exist in the text, like the ones of missing required properties, resolve
+/// to their closest existing parent.
+pub fn locate(source: &str, path: &str) -> Option<Span> {
+    let keys: Vec<String> = path.split("/").skip(1).map(error::unescape_key).collect();
+    let segments: Vec<&str> = keys.iter().map(|key| key.as_ref()).collect();
+
+    let mut scanner = Scanner { source: source.as_bytes(), pos: 0 };
+    let (start, end) = try_opt!(scanner.find(&segments));
+    let before = &source[..start];
+    let line_start = before.rfind("\n").map(|idx| idx + 1).unwrap_or(0);
+    Some(Span {
+        start: start,
+        end: end,
+        line: before.matches("\n").count() + 1,
+        column: source[line_start..start].chars().count() + 1
+    })
+}
+
+/// Renders every error of the state with the line of the source it points at.
+/// `name` is the name of the file shown in the report.
+pub fn render(source: &str, name: &str, state: &validators::ValidationState, style: Style) -> String {
+    let mut report = String::new();
+
+    for error in state.errors.iter() {
+        render_error(&mut report, source, name, &**error, style);
+    }
+
+    report
+}
+
+fn paint(text: &str, color: &str, style: Style) -> String {
+    match style {
+        Style::Plain => text.to_string(),
+        Style::Ansi => format!("\x1b[{}m{}\x1b[0m", color, text)
+    }
+}
+
+const RED: &'static str = "1;31";
+const BLUE: &'static str = "1;34";
+const BOLD: &'static str = "1";
+
+fn render_error(report: &mut String, source: &str, name: &str, error: &ValicoError, style: Style) {
+    let message = error.get_detail().unwrap_or(error.get_title());
+    report.push_str(&format!("{}{} {}\n",
+        paint(&format!("error[{}]", error.get_code()), RED, style),
+        paint(":", BOLD, style),
+        paint(message, BOLD, style)
+    ));
+
+    let span = match locate(source, error.get_path()) {
+        Some(span) => span,
+        None => {
+            report.push_str(&format!("  {} {}\n\n", paint("-->", BLUE, style), name));
+            return
+        }
+    };
+
+    let line = source.lines().nth(span.line - 1).unwrap_or("");
+    let gutter = span.line.to_string();
+    let padding = " ".repeat(gutter.len());
+
+    // The underline stops at the end of the line for values spanning several lines
+    let line_rest = line.chars().count().saturating_sub(span.column - 1);
+    let width = ::std::cmp::max(1, ::std::cmp::min(source[span.start..span.end].chars().count(), line_rest));
+
+    report.push_str(&format!("{} {} {}:{}:{}\n", padding, paint("-->", BLUE, style), name, span.line, span.column));
+    report.push_str(&format!("{} {}\n", padding, paint("|", BLUE, style)));
+    report.push_str(&format!("{} {} {}\n", paint(&gutter, BLUE, style), paint("|", BLUE, style), line));
+    report.push_str(&format!("{} {} {}{} {}\n\n",
+        padding,
+        paint("|", BLUE, style),
+        " ".repeat(span.column - 1),
+        paint(&"^".repeat(width), RED, style),
+        paint(error.get_path(), RED, style)
+    ));
+}
+
+/// Walks the JSON text without building the values. It expects valid JSON and
+/// gives up on anything else.
+struct Scanner<'a> {
+    source: &'a [u8],
+    pos: usize
+}
+
+impl<'a> Scanner<'a> {
+    fn peek(&self) -> Option<u8> {
+        self.source.get(self.pos).cloned()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(byte) = self.peek() {
+            match byte {
+                b' ' | b'\t' | b'\n' | b'\r' => self.pos += 1,
+                _ => break
+            }
+        }
+    }
+
+    fn expect(&mut self, expected: u8) -> Option<()> {
+        self.skip_whitespace();
+        if self.peek() == Some(expected) {
+            self.pos += 1;
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the span of the value at the path, starting at the current value, or
+    /// of its deepest existing parent when the path goes past the text.
+    fn find(&mut self, path: &[&str]) -> Option<(usize, usize)> {
+        self.skip_whitespace();
+        let start = self.pos;
+
+        let index = path.get(0).and_then(|key| key.parse::<usize>().ok());
+        match self.peek() {
+            Some(b'{') if path.len() > 0 => {
+                self.pos += 1;
+                self.skip_whitespace();
+
+                while self.peek() != Some(b'}') {
+                    self.skip_whitespace();
+                    let key = try_opt!(self.string());
+                    try_opt!(self.expect(b':'));
+
+                    if key == path[0] {
+                        return self.find(&path[1..])
+                    }
+
+                    try_opt!(self.skip_member());
+                }
+            },
+            Some(b'[') if index.is_some() => {
+                self.pos += 1;
+                self.skip_whitespace();
+
+                let mut current = 0;
+                while self.peek() != Some(b']') {
+                    if current == index.unwrap() {
+                        return self.find(&path[1..])
+                    }
+
+                    try_opt!(self.skip_member());
+                    current += 1;
+                }
+            },
+            _ => {
+                try_opt!(self.skip_value());
+                return Some((start, self.pos))
+            }
+        }
+
+        // The closing bracket of a container without the key
+        self.pos += 1;
+        Some((start, self.pos))
+    }
+
+    /// Skips a value of an object or an array and the comma after it.
+    fn skip_member(&mut self) -> Option<()> {
+        self.skip_whitespace();
+        try_opt!(self.skip_value());
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b',') => { self.pos += 1; Some(()) },
+            Some(b'}') | Some(b']') => Some(()),
+            _ => None
+        }
+    }
+
+    /// Reads a string, unescaping it.
+    fn string(&mut self) -> Option<String> {
+        if self.peek() != Some(b'"') { return None }
+        self.pos += 1;
+
+        let mut bytes = vec![];
+        loop {
+            let byte = try_opt!(self.peek());
+            self.pos += 1;
+            match byte {
+                b'"' => break,
+                b'\\' => {
+                    let escaped = try_opt!(self.peek());
+                    self.pos += 1;
+                    let unescaped = match escaped {
+                        b'n' => '\n',
+                        b't' => '\t',
+                        b'r' => '\r',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'u' => {
+                            let mut code = try_opt!(self.hex_escape());
+                            // Astral characters are escaped as a pair of surrogates
+                            if code >= 0xd800 && code < 0xdc00 && self.source.get(self.pos..self.pos + 2) == Some(&b"\\u"[..]) {
+                                let pos = self.pos;
+                                self.pos += 2;
+                                match try_opt!(self.hex_escape()) {
+                                    low if low >= 0xdc00 && low < 0xe000 => code = 0x10000 + ((code - 0xd800) << 10) + (low - 0xdc00),
+                                    _ => self.pos = pos
+                                }
+                            }
+                            ::std::char::from_u32(code).unwrap_or('\u{fffd}')
+                        },
+                        other => other as char
+                    };
+                    let mut buffer = [0; 4];
+                    bytes.extend_from_slice(unescaped.encode_utf8(&mut buffer).as_bytes());
+                },
+                other => bytes.push(other)
+            }
+        }
+
+        String::from_utf8(bytes).ok()
+    }
+
+    /// Reads the four hex digits of a `\u` escape.
+    fn hex_escape(&mut self) -> Option<u32> {
+        let hex = try_opt!(self.source.get(self.pos..self.pos + 4));
+        self.pos += 4;
+        u32::from_str_radix(try_opt!(::std::str::from_utf8(hex).ok()), 16).ok()
+    }
+
+    fn skip_value(&mut self) -> Option<()> {
+        match try_opt!(self.peek()) {
+            b'"' => self.string().map(|_| ()),
+            open @ b'{' | open @ b'[' => {
+                let close = if open == b'{' { b'}' } else { b']' };
+                self.pos += 1;
+                let mut depth = 1;
+                while depth > 0 {
+                    match try_opt!(self.peek()) {
+                        b'"' => { try_opt!(self.string()); },
+                        byte => {
+                            if byte == open {
+                                depth += 1;
+                            } else if byte == close {
+                                depth -= 1;
+                            }
+                            self.pos += 1;
+                        }
+                    }
+                }
+                Some(())
+            },
+            _ => {
+                let start = self.pos;
+                while let Some(byte) = self.peek() {
+                    match byte {
+                        b',' | b'}' | b']' | b' ' | b'\t' | b'\n' | b'\r' => break,
+                        _ => self.pos += 1
+                    }
+                }
+                if self.pos > start { Some(()) } else { None }
+            }
+        }
+    }
+}
+
+#[cfg(test)] use super::scope;
+#[cfg(test)] use serde_json::from_str;
+
+#[cfg(test)]
+static SOURCE: &'static str = r#"{
+    "name": "api",
+    "port": 70000,
+    "hosts": ["a", {"name": "b\"c", "tags": [1, 2]}],
+    "paths": {"/api": 1, "a~b": 2}
+}"#;
+
+#[test]
+fn locate_values() {
+    assert_eq!(locate(SOURCE, ""), Some(Span { start: 0, end: SOURCE.len(), line: 1, column: 1 }));
+
+    let port = locate(SOURCE, "/port").unwrap();
+    assert_eq!((port.line, port.column), (3, 13));
+    assert_eq!(&SOURCE[port.start..port.end], "70000");
+
+    let tag = locate(SOURCE, "/hosts/1/tags/1").unwrap();
+    assert_eq!((tag.line, &SOURCE[tag.start..tag.end]), (4, "2"));
+
+    // Keys with `/` and `~` are escaped in the path
+    let api = locate(SOURCE, "/paths/~1api").unwrap();
+    assert_eq!((api.line, &SOURCE[api.start..api.end]), (5, "1"));
+    let tilde = locate(SOURCE, "/paths/a~0b").unwrap();
+    assert_eq!(&SOURCE[tilde.start..tilde.end], "2");
+
+    // Missing values point at their parent
+    let missing = locate(SOURCE, "/hosts/1/missing").unwrap();
+    assert_eq!(&SOURCE[missing.start..missing.end], r#"{"name": "b\"c", "tags": [1, 2]}"#);
+    let missing = locate(SOURCE, "/hosts/5/name").unwrap();
+    assert_eq!(&SOURCE[missing.start..missing.end], r#"["a", {"name": "b\"c", "tags": [1, 2]}]"#);
+
+    // Escaped astral characters are decoded from their surrogate pairs
+    let source = r#"{"\ud83d\ude00": 1, "\ud83d": 2}"#;
+    let emoji = locate(source, "/\u{1f600}").unwrap();
+    assert_eq!(&source[emoji.start..emoji.end], "1");
+    let lone = locate(source, "/\u{fffd}").unwrap();
+    assert_eq!(&source[lone.start..lone.end], "2");
+}
+
+#[test]
+fn render_report() {
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(from_str(r#"{
+        "properties": { "port": { "maximum": 65535 } },
+        "required": ["version"]
+    }"#).unwrap(), true).ok().unwrap();
+
+    let state = schema.validate(&from_str(SOURCE).unwrap());
+    let report = render(SOURCE, "config.json", &state, Style::Plain);
+
+    assert!(report.contains("error[maximum]: Maximum condition is not met\n  --> config.json:3:13\n"));
+    assert!(report.contains("3 |     \"port\": 70000,\n  |             ^^^^^ /port\n"));
+    assert!(report.contains("error[required]: This property is required\n  --> config.json:1:1\n"));
+
+    let schema = scope.compile_and_return(from_str(r#"{
+        "properties": { "paths": { "additionalProperties": { "maximum": 1 } } }
+    }"#).unwrap(), true).ok().unwrap();
+    let report = render(SOURCE, "config.json", &schema.validate(&from_str(SOURCE).unwrap()), Style::Plain);
+    assert!(report.contains("5 |     \"paths\": {\"/api\": 1, \"a~b\": 2}\n  |                                 ^ /paths/a~0b\n"));
+
+    let colored = render(SOURCE, "config.json", &state, Style::Ansi);
+    assert!(colored.contains("\x1b[1;31merror[maximum]\x1b[0m"));
+}
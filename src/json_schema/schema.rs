@@ -1,38 +1,363 @@
 use url;
+use std::cell;
 use std::collections;
+use std::sync;
 use serde_json::{Value};
 use phf;
 use std::ops;
+use std::error;
+use std::fmt;
+use std::mem;
 
 use super::helpers;
 use super::scope;
 use super::keywords;
 use super::validators;
+use super::error_message;
+use super::ecma_regex;
+use super::errors;
 
 #[derive(Debug)]
 pub struct WalkContext<'a> {
     pub url: &'a url::Url,
+    pub document: &'a sync::Arc<url::Url>,
     pub fragment: Vec<String>,
-    pub scopes: &'a mut collections::HashMap<String, Vec<String>>
+    pub scopes: &'a mut collections::HashMap<String, Vec<String>>,
+    pub refs: &'a mut Vec<url::Url>,
+    pub links: &'a cell::RefCell<Vec<Link>>,
+    /// The errors found so far, compilation goes on to find the others.
+    pub errors: &'a cell::RefCell<Vec<SchemaError>>,
+    pub draft: super::Draft,
+    pub format_mode: keywords::format::FormatMode,
+    pub limits: Limits,
+    /// Number of subschemas of the document walked so far.
+    pub subschemas: &'a cell::Cell<usize>,
+    /// Number of schemas the walked value is nested in.
+    pub depth: usize
 }
 
 impl<'a> WalkContext<'a> {
+    /// Creates the link to the subschema at the url that validators follow instead
+    /// of resolving the url on every validation.
+    pub fn link(&self, url: url::Url) -> Link {
+        let link = Link::new(url, self.document.clone(), self.pointer());
+        self.links.borrow_mut().push(link.clone());
+        link
+    }
+
+    /// The location of the keyword in the schema, which its errors are reported at.
+    pub fn keyword_fragment(&self, keyword: &str) -> Vec<String> {
+        let mut fragment = self.fragment.clone();
+        fragment.push(keyword.to_string());
+        fragment
+    }
+
+    /// JSON pointer of the current (sub)schema in its document.
+    pub fn pointer(&self) -> String {
+        super::super::common::error::to_pointer(&self.fragment)
+    }
+
+    /// A `SchemaError::Malformed` at the current (sub)schema.
+    pub fn malformed(&self, detail: String) -> SchemaError {
+        SchemaError::Malformed {
+            url: (**self.document).clone(),
+            pointer: self.pointer(),
+            detail: detail
+        }
+    }
+
+    /// The error of a regular expression of the current (sub)schema that is valid but
+    /// can't be used, because it is unsupported or too big.
+    pub fn regex_error(&self, pattern: &str, error: ecma_regex::RegexError) -> SchemaError {
+        match error {
+            ecma_regex::RegexError::TooBig(limit) => SchemaError::RegexTooBig {
+                url: (**self.document).clone(),
+                pointer: self.pointer(),
+                pattern: pattern.to_string(),
+                limit: limit
+            },
+            error => SchemaError::UnsupportedRegex {
+                url: (**self.document).clone(),
+                pointer: self.pointer(),
+                pattern: pattern.to_string(),
+                error: error
+            }
+        }
+    }
+
     pub fn escaped_fragment(&self) -> String {
         helpers::connect(self.fragment.iter().map(|s| s.as_ref()).collect::<Vec<&str>>().as_ref())
     }
+
+    /// Whether the value can be used as a subschema in the current dialect.
+    pub fn is_schema(&self, value: &Value) -> bool {
+        value.is_object() || (value.is_boolean() && self.draft.allows_boolean_schemas())
+    }
 }
 
+/// An error of the compilation of a schema. Every error has the url of the document
+/// it was found in and the JSON pointer of the (sub)schema in that document.
 #[derive(Debug)]
 #[allow(missing_copy_implementations)]
 pub enum SchemaError {
-    WrongId,
-    IdConflicts,
-    NotAnObject,
-    UrlParseError(url::ParseError),
-    UnknownKey(String),
+    /// The id of a document can't have a fragment.
+    WrongId {
+        url: url::Url,
+        pointer: String
+    },
+    /// The scope already has a document with this id.
+    IdConflicts {
+        url: url::Url,
+        pointer: String
+    },
+    NotAnObject {
+        url: url::Url,
+        pointer: String
+    },
+    UrlParseError {
+        url: url::Url,
+        pointer: String,
+        error: url::ParseError
+    },
+    UnknownKey {
+        url: url::Url,
+        pointer: String,
+        key: String
+    },
+    /// The format isn't known, which is only an error with `FormatMode::Strict`.
+    UnknownFormat {
+        url: url::Url,
+        pointer: String,
+        format: String
+    },
     Malformed {
-        path: String,
+        url: url::Url,
+        pointer: String,
+        detail: String
+    },
+    /// The regular expression uses an ECMA-262 construct that can't be translated,
+    /// like a backreference.
+    UnsupportedRegex {
+        url: url::Url,
+        pointer: String,
+        pattern: String,
+        error: ecma_regex::RegexError
+    },
+    /// The compiled regular expression is bigger than `Limits::regex_size`.
+    RegexTooBig {
+        url: url::Url,
+        pointer: String,
+        pattern: String,
+        limit: usize
+    },
+    /// The subschema is nested deeper than `Limits::depth`.
+    TooDeep {
+        url: url::Url,
+        pointer: String,
+        limit: usize
+    },
+    /// The document has more subschemas than `Limits::subschemas`, the pointer is
+    /// the one of the first subschema over the limit.
+    TooManySubschemas {
+        url: url::Url,
+        pointer: String,
+        limit: usize
+    },
+    LoadError {
+        url: url::Url,
+        pointer: String,
         detail: String
+    },
+    /// The reference of the (sub)schema doesn't resolve to a schema of the scope or
+    /// of its loaders.
+    UnresolvedRef {
+        url: url::Url,
+        pointer: String,
+        reference: url::Url
+    },
+    /// The schema doesn't match the meta-schema at `meta_schema`.
+    Invalid {
+        url: url::Url,
+        pointer: String,
+        meta_schema: url::Url,
+        state: validators::ValidationState
+    },
+    /// Every error found in a schema that has several.
+    Multiple(Vec<SchemaError>)
+}
+
+impl SchemaError {
+    /// The url of the document, `None` for `Multiple`.
+    pub fn url(&self) -> Option<&url::Url> {
+        match *self {
+            SchemaError::WrongId { ref url, .. } |
+            SchemaError::IdConflicts { ref url, .. } |
+            SchemaError::NotAnObject { ref url, .. } |
+            SchemaError::UrlParseError { ref url, .. } |
+            SchemaError::UnknownKey { ref url, .. } |
+            SchemaError::UnknownFormat { ref url, .. } |
+            SchemaError::Malformed { ref url, .. } |
+            SchemaError::UnsupportedRegex { ref url, .. } |
+            SchemaError::RegexTooBig { ref url, .. } |
+            SchemaError::TooDeep { ref url, .. } |
+            SchemaError::TooManySubschemas { ref url, .. } |
+            SchemaError::LoadError { ref url, .. } |
+            SchemaError::UnresolvedRef { ref url, .. } |
+            SchemaError::Invalid { ref url, .. } => Some(url),
+            SchemaError::Multiple(_) => None
+        }
+    }
+
+    /// The JSON pointer of the (sub)schema in the document, `None` for `Multiple`.
+    pub fn pointer(&self) -> Option<&str> {
+        match *self {
+            SchemaError::WrongId { ref pointer, .. } |
+            SchemaError::IdConflicts { ref pointer, .. } |
+            SchemaError::NotAnObject { ref pointer, .. } |
+            SchemaError::UrlParseError { ref pointer, .. } |
+            SchemaError::UnknownKey { ref pointer, .. } |
+            SchemaError::UnknownFormat { ref pointer, .. } |
+            SchemaError::Malformed { ref pointer, .. } |
+            SchemaError::UnsupportedRegex { ref pointer, .. } |
+            SchemaError::RegexTooBig { ref pointer, .. } |
+            SchemaError::TooDeep { ref pointer, .. } |
+            SchemaError::TooManySubschemas { ref pointer, .. } |
+            SchemaError::LoadError { ref pointer, .. } |
+            SchemaError::UnresolvedRef { ref pointer, .. } |
+            SchemaError::Invalid { ref pointer, .. } => Some(pointer),
+            SchemaError::Multiple(_) => None
+        }
+    }
+
+    /// The errors, with the ones of `Multiple` flattened.
+    pub fn errors(&self) -> Vec<&SchemaError> {
+        match *self {
+            SchemaError::Multiple(ref errors) => errors.iter().flat_map(|error| error.errors()).collect(),
+            _ => vec![self]
+        }
+    }
+
+    /// Joins the errors into one, which is `Multiple` if there are several.
+    pub fn join(mut errors: Vec<SchemaError>) -> Option<SchemaError> {
+        match errors.len() {
+            0 => None,
+            1 => errors.pop(),
+            _ => Some(SchemaError::Multiple(errors))
+        }
+    }
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let (url, pointer) = match (self.url(), self.pointer()) {
+            (Some(url), Some(pointer)) => (url, pointer),
+            _ => {
+                let errors = self.errors();
+                try!(write!(fmt, "{} errors in the schema", errors.len()));
+                for error in errors {
+                    try!(write!(fmt, "\n{}", error));
+                }
+                return Ok(())
+            }
+        };
+
+        if pointer.len() > 0 {
+            let mut document = url.clone();
+            document.set_fragment(None);
+            try!(write!(fmt, "{}#{}: ", document, pointer));
+        } else {
+            try!(write!(fmt, "{}: ", url));
+        }
+
+        match *self {
+            SchemaError::WrongId { .. } => write!(fmt, "the id of a document can't have a fragment"),
+            SchemaError::IdConflicts { .. } => write!(fmt, "the scope already has a schema with this id"),
+            SchemaError::NotAnObject { .. } => write!(fmt, "the schema must be an object"),
+            SchemaError::UrlParseError { ref error, .. } => write!(fmt, "invalid url: {}", error),
+            SchemaError::UnknownKey { ref key, .. } => write!(fmt, "unknown keyword `{}`", key),
+            SchemaError::UnknownFormat { ref format, .. } => write!(fmt, "unknown format `{}`", format),
+            SchemaError::Malformed { ref detail, .. } => write!(fmt, "{}", detail),
+            SchemaError::UnsupportedRegex { ref pattern, ref error, .. } => write!(fmt, "can't use the regular expression `{}`: {}", pattern, error),
+            SchemaError::RegexTooBig { ref pattern, limit, .. } => {
+                write!(fmt, "the regular expression `{}` is bigger than the limit of {} bytes once compiled", pattern, limit)
+            },
+            SchemaError::TooDeep { limit, .. } => write!(fmt, "the subschema is nested deeper than the limit of {}", limit),
+            SchemaError::TooManySubschemas { limit, .. } => write!(fmt, "the document has more than {} subschemas", limit),
+            SchemaError::LoadError { ref detail, .. } => write!(fmt, "can't load the document: {}", detail),
+            SchemaError::UnresolvedRef { ref reference, .. } => write!(fmt, "can't resolve the reference {}", reference),
+            SchemaError::Invalid { ref meta_schema, ref state, .. } => {
+                try!(write!(fmt, "the schema doesn't match the meta-schema {}", meta_schema));
+                for error in state.errors.iter() {
+                    try!(write!(fmt, "\n  {}: {}", error.get_path(), error.get_detail().unwrap_or(error.get_title())));
+                }
+                Ok(())
+            },
+            SchemaError::Multiple(_) => unreachable!()
+        }
+    }
+}
+
+impl error::Error for SchemaError {
+    fn source(&self) -> Option<&(error::Error + 'static)> {
+        match *self {
+            SchemaError::UrlParseError { ref error, .. } => Some(error),
+            SchemaError::UnsupportedRegex { ref error, .. } => Some(error),
+            _ => None
+        }
+    }
+}
+
+/// A reference to a (sub)schema, resolved once when its document is added to the
+/// `Scope`.
+#[derive(Debug, Clone)]
+pub struct Link {
+    pub url: url::Url,
+    /// Document and JSON pointer of the (sub)schema making the reference.
+    document: sync::Arc<url::Url>,
+    pointer: String,
+    target: sync::Arc<sync::OnceLock<sync::Weak<Schema>>>
+}
+
+impl Link {
+    pub fn new(url: url::Url, document: sync::Arc<url::Url>, pointer: String) -> Link {
+        Link {
+            url: url,
+            document: document,
+            pointer: pointer,
+            target: sync::Arc::new(sync::OnceLock::new())
+        }
+    }
+
+    /// The linked schema. Adding a document fails unless all its references are
+    /// linked, and validation keeps the scope alive, so a validator never follows
+    /// a link that isn't.
+    pub fn get(&self) -> sync::Arc<Schema> {
+        match self.target() {
+            Some(schema) => schema,
+            None => unreachable!("The reference to {} is validated without being linked", self.url)
+        }
+    }
+
+    pub fn is_linked(&self) -> bool {
+        self.target().is_some()
+    }
+
+    fn target(&self) -> Option<sync::Arc<Schema>> {
+        self.target.get().and_then(|target| target.upgrade())
+    }
+
+    /// Links to the schema. A link is only set once, when its document is added.
+    pub fn set(&self, schema: &sync::Arc<Schema>) {
+        let _ = self.target.set(sync::Arc::downgrade(schema));
+    }
+
+    /// The error of a link whose url doesn't resolve.
+    pub fn unresolved(&self) -> SchemaError {
+        SchemaError::UnresolvedRef {
+            url: (*self.document).clone(),
+            pointer: self.pointer.clone(),
+            reference: self.url.clone()
+        }
     }
 }
 
@@ -59,11 +384,101 @@ impl<'a> ScopedSchema<'a> {
     }
 
     pub fn validate(&self, data: &Value) -> validators::ValidationState {
-        return self.schema.validate_in_scope(data, "", self.scope);
+        return self.schema.validate_in_scope(data, "", self.scope, &validators::DynamicScope::new());
+    }
+
+    pub fn validate_in(&self, data: &Value, path: &str) -> validators::ValidationState {
+        return self.schema.validate_in_scope(data, path, self.scope, &validators::DynamicScope::new());
+    }
+
+    /// Checks the data without collecting the errors, stopping at the first one.
+    pub fn is_valid(&self, data: &Value) -> bool {
+        let found = cell::Cell::new(0);
+        self.schema.validate_in_scope(data, "", self.scope, &validators::DynamicScope::flag(&found)).is_valid()
+    }
+
+    /// Validates the data, stopping once `max_errors` errors are found.
+    pub fn validate_with_max_errors(&self, data: &Value, max_errors: usize) -> validators::ValidationState {
+        let found = cell::Cell::new(0);
+        self.schema.validate_in_scope(data, "", self.scope, &validators::DynamicScope::with_max_errors(max_errors, &found))
+    }
+
+    /// Validates the data and collects the `title`, `description`, `default`, `examples`,
+    /// `readOnly`, `deprecated` and `format` annotations of the schemas it is valid
+    /// against into the `annotations` of the state.
+    pub fn annotate(&self, data: &Value) -> validators::ValidationState {
+        return self.schema.validate_in_scope(data, "", self.scope, &validators::DynamicScope::annotating());
+    }
+
+    /// Validates the data and records every schema applied to it, valid or not, into
+    /// the `evaluations` of the state, for the full hierarchy of `OutputFormat::Verbose`.
+    pub fn validate_verbose(&self, data: &Value) -> validators::ValidationState {
+        return self.schema.validate_in_scope(data, "", self.scope, &validators::DynamicScope::verbose());
+    }
+
+    /// Validates the data as a part of an ongoing validation that has already entered
+    /// the resources of `dynamic_scope`.
+    pub fn validate_in_dynamic(&self, data: &Value, path: &str, dynamic_scope: &validators::DynamicScope) -> validators::ValidationState {
+        return self.schema.validate_in_scope(data, path, self.scope, dynamic_scope);
+    }
+}
+
+/// Owned handle of a compiled schema and the scope it was compiled in. It is cheap
+/// to clone and can be validated against from several threads at once.
+#[derive(Debug, Clone)]
+pub struct CompiledSchema {
+    scope: sync::Arc<scope::Scope>,
+    schema: sync::Arc<Schema>
+}
+
+impl ops::Deref for CompiledSchema {
+    type Target = Schema;
+
+    fn deref(&self) -> &Schema {
+        &self.schema
+    }
+}
+
+impl CompiledSchema {
+    pub fn new(scope: sync::Arc<scope::Scope>, schema: sync::Arc<Schema>) -> CompiledSchema {
+        CompiledSchema {
+            scope: scope,
+            schema: schema
+        }
+    }
+
+    pub fn scope(&self) -> &sync::Arc<scope::Scope> {
+        &self.scope
+    }
+
+    pub fn validate(&self, data: &Value) -> validators::ValidationState {
+        return self.schema.validate_in_scope(data, "", &self.scope, &validators::DynamicScope::new());
     }
 
     pub fn validate_in(&self, data: &Value, path: &str) -> validators::ValidationState {
-        return self.schema.validate_in_scope(data, path, self.scope);
+        return self.schema.validate_in_scope(data, path, &self.scope, &validators::DynamicScope::new());
+    }
+
+    /// Same as `ScopedSchema::is_valid`.
+    pub fn is_valid(&self, data: &Value) -> bool {
+        let found = cell::Cell::new(0);
+        self.schema.validate_in_scope(data, "", &self.scope, &validators::DynamicScope::flag(&found)).is_valid()
+    }
+
+    /// Same as `ScopedSchema::validate_with_max_errors`.
+    pub fn validate_with_max_errors(&self, data: &Value, max_errors: usize) -> validators::ValidationState {
+        let found = cell::Cell::new(0);
+        self.schema.validate_in_scope(data, "", &self.scope, &validators::DynamicScope::with_max_errors(max_errors, &found))
+    }
+
+    /// Same as `ScopedSchema::annotate`.
+    pub fn annotate(&self, data: &Value) -> validators::ValidationState {
+        return self.schema.validate_in_scope(data, "", &self.scope, &validators::DynamicScope::annotating());
+    }
+
+    /// Same as `ScopedSchema::validate_verbose`.
+    pub fn validate_verbose(&self, data: &Value) -> validators::ValidationState {
+        return self.schema.validate_in_scope(data, "", &self.scope, &validators::DynamicScope::verbose());
     }
 }
 
@@ -73,16 +488,64 @@ pub struct Schema {
     pub id: Option<url::Url>,
     schema: Option<url::Url>,
     original: Value,
-    tree: collections::BTreeMap<String, Schema>,
+    tree: collections::BTreeMap<String, sync::Arc<Schema>>,
     validators: validators::Validators,
-    scopes: collections::HashMap<String, Vec<String>>
+    scopes: collections::HashMap<String, Vec<String>>,
+    refs: Vec<url::Url>,
+    links: Vec<Link>,
+    document: sync::Arc<url::Url>,
+    fragment: Vec<String>,
+    error_message: Option<error_message::ErrorMessage>,
+    /// Whether a validator of the schema reads what the others have evaluated.
+    uses_evaluated: bool,
+    /// The schemas of the resource declared with `$dynamicAnchor`, by name.
+    dynamic_anchors: collections::HashMap<String, Link>
 }
 
 include!(concat!(env!("OUT_DIR"), "/codegen.rs"));
 
+/// Keywords whose values are collected as annotations.
+const ANNOTATION_KEYS: [&'static str; 7] = ["title", "description", "default", "examples", "readOnly", "deprecated", "format"];
+
+/// Limits on the resources used by schemas, for schemas from untrusted sources.
+/// Only `reference_depth` is on by default.
+#[derive(Copy, Debug, Clone, PartialEq)]
+pub struct Limits {
+    /// Maximum size in bytes of each compiled `pattern` and `patternProperties`
+    /// regular expression, instead of the 10 MB allowed by the `regex` crate.
+    pub regex_size: Option<usize>,
+    /// Maximum nesting depth of the subschemas of a document, counted in schemas:
+    /// the subschema at `/properties/a/items` is at depth 2.
+    pub depth: Option<usize>,
+    /// Maximum number of subschemas of a document, not counting the root.
+    pub subschemas: Option<usize>,
+    /// Maximum number of references (`$ref`, `$recursiveRef` and `$dynamicRef`)
+    /// followed one inside the other during a validation, which stops the ones that
+    /// loop like `{"$ref": "#"}`.
+    pub reference_depth: Option<usize>
+}
+
+impl Default for Limits {
+    fn default() -> Limits {
+        Limits {
+            regex_size: None,
+            depth: None,
+            subschemas: None,
+            reference_depth: Some(DEFAULT_REFERENCE_DEPTH)
+        }
+    }
+}
+
+/// Default of `Limits::reference_depth`, well over the nesting that serde_json
+/// allows when parsing instances.
+const DEFAULT_REFERENCE_DEPTH: usize = 256;
+
 pub struct CompilationSettings<'a> {
     pub keywords: &'a keywords::KeywordMap,
-    pub ban_unknown_keywords: bool
+    pub ban_unknown_keywords: bool,
+    pub draft: super::Draft,
+    pub format_mode: keywords::format::FormatMode,
+    pub limits: Limits
 }
 
 impl<'a> CompilationSettings<'a> {
@@ -90,87 +553,183 @@ impl<'a> CompilationSettings<'a> {
         CompilationSettings {
             keywords: keywords,
             ban_unknown_keywords: ban_unknown_keywords,
+            draft: super::Draft::V4,
+            format_mode: keywords::format::FormatMode::default_for(super::Draft::V4),
+            limits: Limits::default()
+        }
+    }
+
+    pub fn with_draft(keywords: &'a keywords::KeywordMap, ban_unknown_keywords: bool, draft: super::Draft) -> CompilationSettings<'a> {
+        CompilationSettings {
+            keywords: keywords,
+            ban_unknown_keywords: ban_unknown_keywords,
+            draft: draft,
+            format_mode: keywords::format::FormatMode::default_for(draft),
+            limits: Limits::default()
         }
     }
+
+    /// Whether a value is walked as a subschema. Objects and arrays always are, but
+    /// booleans only in the positions where a schema is expected, so the value of
+    /// `uniqueItems` isn't taken for one.
+    fn is_subschema(&self, value: &Value, schema_position: bool) -> bool {
+        value.is_object() || value.is_array() ||
+            (value.is_boolean() && schema_position && self.draft.allows_boolean_schemas())
+    }
 }
 
 impl Schema {
     fn compile(def: Value, external_id: Option<url::Url>, settings: CompilationSettings) -> Result<Schema, SchemaError> {
-        if !def.is_object() {
-            return Err(SchemaError::NotAnObject)
+        let id = match external_id {
+            Some(id) => id,
+            None => try!(helpers::parse_url_key(settings.draft.id_key(), &def).map_err(|error| {
+                SchemaError::UrlParseError { url: helpers::generate_id(), pointer: "".to_string(), error: error }
+            })).unwrap_or_else(|| helpers::generate_id())
+        };
+
+        if !def.is_object() && !(def.is_boolean() && settings.draft.allows_boolean_schemas()) {
+            return Err(SchemaError::NotAnObject { url: id, pointer: "".to_string() })
         }
 
-        let id = if external_id.is_some() {
-            external_id.unwrap()
-        } else {
-            try!(helpers::parse_url_key("id", &def)).clone().unwrap_or_else(|| helpers::generate_id())
-        };
+        if def.is_boolean() {
+            return Ok(Schema {
+                id: Some(id.clone()),
+                schema: None,
+                validators: Schema::compile_boolean(&def, &[]),
+                original: def,
+                tree: collections::BTreeMap::new(),
+                scopes: collections::HashMap::new(),
+                refs: vec![],
+                links: vec![],
+                document: sync::Arc::new(id),
+                fragment: vec![],
+                error_message: None,
+                uses_evaluated: false,
+                dynamic_anchors: collections::HashMap::new()
+            })
+        }
 
-        let schema = try!(helpers::parse_url_key("$schema", &def));
+        let links = cell::RefCell::new(vec![]);
+        let errors = cell::RefCell::new(vec![]);
+        let subschemas = cell::Cell::new(0);
+        let document = sync::Arc::new(id.clone());
 
-        let (tree, mut scopes) = {
+        let schema = helpers::parse_url_key("$schema", &def).unwrap_or_else(|error| {
+            errors.borrow_mut().push(SchemaError::UrlParseError { url: id.clone(), pointer: "".to_string(), error: error });
+            None
+        });
+
+        let (tree, mut scopes, mut refs) = {
             let mut tree = collections::BTreeMap::new();
             let obj = def.as_object().unwrap();
 
             let mut scopes = collections::HashMap::new();
+            let mut refs = vec![];
 
             for (key, value) in obj.iter() {
-                if !value.is_object() && !value.is_array() { continue; }
+                if !settings.is_subschema(value, SCHEMA_KEYS.contains(&key[..])) { continue; }
                 if FINAL_KEYS.contains(&key[..]) { continue; }
 
                 let mut context = WalkContext {
                     url: &id,
+                    document: &document,
                     fragment: vec![key.clone()],
-                    scopes: &mut scopes
+                    scopes: &mut scopes,
+                    refs: &mut refs,
+                    links: &links,
+                    errors: &errors,
+                    draft: settings.draft,
+                    format_mode: settings.format_mode,
+                    limits: settings.limits,
+                    subschemas: &subschemas,
+                    depth: 1
                 };
 
-                let scheme = try!(Schema::compile_sub(
+                let scheme = Schema::compile_sub(
                     value.clone(),
                     &mut context,
                     &settings,
-                    !NON_SCHEMA_KEYS.contains(&key[..])
-                ));
+                    !value.is_array() && !NON_SCHEMA_KEYS.contains(&key[..])
+                );
+
+                tree.insert(helpers::encode(key), sync::Arc::new(scheme));
+            }
 
-                tree.insert(helpers::encode(key), scheme);
+            for anchor in Schema::parse_anchors(&def, &id, settings.draft) {
+                scopes.insert(anchor.into_string(), vec![]);
             }
 
-            (tree, scopes)
+            Schema::collect_refs(&def, &id, &mut refs);
+
+            (tree, scopes, refs)
         };
 
-        let validators = try!(Schema::compile_keywords(&def, &WalkContext {
+        let context = WalkContext {
             url: &id,
+            document: &document,
             fragment: vec![],
             scopes: &mut scopes,
-        }, &settings));
+            refs: &mut refs,
+            links: &links,
+            errors: &errors,
+            draft: settings.draft,
+            format_mode: settings.format_mode,
+            limits: settings.limits,
+            subschemas: &subschemas,
+            depth: 0
+        };
+        let validators = Schema::compile_keywords(&def, &context, &settings);
+        let error_message = error_message::compile(&def, &context).unwrap_or_else(|error| {
+            errors.borrow_mut().push(error);
+            None
+        });
+        let dynamic_anchors = Schema::link_dynamic_anchors(&def, &tree, &id, &context);
+
+        match SchemaError::join(errors.into_inner()) {
+            Some(error) => return Err(error),
+            None => ()
+        }
 
+        let uses_evaluated = validators.iter().any(|validator| validator.uses_evaluated());
         let schema = Schema {
             id: Some(id),
             schema: schema,
             original: def,
             tree: tree,
             validators: validators,
-            scopes: scopes
+            scopes: scopes,
+            refs: refs,
+            links: links.into_inner(),
+            document: document,
+            fragment: vec![],
+            error_message: error_message,
+            uses_evaluated: uses_evaluated,
+            dynamic_anchors: dynamic_anchors
         };
 
         Ok(schema)
     }
 
-    fn compile_keywords(def: &Value, context: &WalkContext, settings: &CompilationSettings) -> Result<validators::Validators, SchemaError> {
+    fn compile_keywords(def: &Value, context: &WalkContext, settings: &CompilationSettings) -> validators::Validators {
         let mut validators = vec![];
         let mut keys: collections::HashSet<&str> = def.as_object().unwrap().keys().map(|key| key.as_ref()).collect();
         let mut not_consumed = collections::HashSet::new();
 
         loop {
-            let key = keys.iter().next().cloned();
+            // Deferred keywords depend on what the other keywords have evaluated,
+            // so they are compiled (and therefore validated) last.
+            let key = keys.iter().find(|key| !DEFERRED_KEYS.contains(&key[..]))
+                .or_else(|| keys.iter().next()).cloned();
             if key.is_some() {
                 let key = key.unwrap();
                 match settings.keywords.get(&key) {
                     Some(keyword) => {
                         keyword.consume(&mut keys);
 
-                        match try!(keyword.keyword.compile(def, context)) {
-                            Some(validator) => validators.push(validator),
-                            None => ()
+                        match keyword.keyword.compile(def, context) {
+                            Ok(Some(validator)) => validators.push(validator),
+                            Ok(None) => (),
+                            Err(error) => context.errors.borrow_mut().push(error)
                         }
                     },
                     None => {
@@ -185,25 +744,81 @@ impl Schema {
             }
         }
 
-        if settings.ban_unknown_keywords && not_consumed.len() > 0 {
-            for key in not_consumed.iter() {
-                if !ALLOW_NON_CONSUMED_KEYS.contains(&key[..]) {
-                    return Err(SchemaError::UnknownKey(key.to_string()))
-                }
-            }
+        let mut not_consumed = not_consumed.into_iter().filter(|key| !ALLOW_NON_CONSUMED_KEYS.contains(&key[..])).collect::<Vec<&str>>();
+        not_consumed.sort();
+
+        for key in not_consumed {
+            context.errors.borrow_mut().push(SchemaError::UnknownKey {
+                url: (**context.document).clone(),
+                pointer: super::super::common::error::to_pointer(&context.keyword_fragment(key)),
+                key: key.to_string()
+            });
         }
 
-        Ok(validators)
+        validators
     }
 
-    fn compile_sub(def: Value, context: &mut WalkContext, keywords: &CompilationSettings, is_schema: bool) -> Result<Schema, SchemaError> {
+    fn compile_sub(def: Value, context: &mut WalkContext, keywords: &CompilationSettings, is_schema: bool) -> Schema {
+
+        if is_schema {
+            context.subschemas.set(context.subschemas.get() + 1);
+        }
+
+        // Documents over the limits aren't walked any further
+        let too_deep = is_schema && context.limits.depth.map(|limit| context.depth > limit).unwrap_or(false);
+        let too_many = context.limits.subschemas.map(|limit| context.subschemas.get() > limit).unwrap_or(false);
+
+        if too_deep || too_many {
+            if too_deep {
+                context.errors.borrow_mut().push(SchemaError::TooDeep {
+                    url: (**context.document).clone(),
+                    pointer: context.pointer(),
+                    limit: context.limits.depth.unwrap()
+                });
+            }
+            if too_many && is_schema && context.subschemas.get() == context.limits.subschemas.unwrap() + 1 {
+                context.errors.borrow_mut().push(SchemaError::TooManySubschemas {
+                    url: (**context.document).clone(),
+                    pointer: context.pointer(),
+                    limit: context.limits.subschemas.unwrap()
+                });
+            }
+
+            return Schema {
+                id: None,
+                schema: None,
+                original: def,
+                tree: collections::BTreeMap::new(),
+                validators: vec![],
+                scopes: collections::HashMap::new(),
+                refs: vec![],
+                links: vec![],
+                document: context.document.clone(),
+                fragment: context.fragment.clone(),
+                error_message: None,
+                uses_evaluated: false,
+                dynamic_anchors: collections::HashMap::new()
+            }
+        }
 
         let mut id = None;
         let mut schema = None;
 
         if is_schema {
-            id = try!(helpers::parse_url_key_with_base("id", &def, context.url));
-            schema = try!(helpers::parse_url_key("$schema", &def));
+            let url_error = |error| SchemaError::UrlParseError {
+                url: (**context.document).clone(),
+                pointer: context.pointer(),
+                error: error
+            };
+
+            match helpers::parse_url_key_with_base(keywords.draft.id_key(), &def, context.url) {
+                Ok(url) => id = url,
+                Err(error) => context.errors.borrow_mut().push(url_error(error))
+            }
+            match helpers::parse_url_key("$schema", &def) {
+                Ok(url) => schema = url,
+                Err(error) => context.errors.borrow_mut().push(url_error(error))
+            }
         }
 
         let tree = {
@@ -214,52 +829,73 @@ impl Schema {
                 let parent_key = &context.fragment[context.fragment.len() - 1];
 
                 for (key, value) in obj.iter() {
-                    if !value.is_object() && !value.is_array() { continue; }
+                    // The values of a map of schemas like `properties` are all schemas
+                    if !keywords.is_subschema(value, !is_schema || SCHEMA_KEYS.contains(&key[..])) { continue; }
                     if !PROPERTY_KEYS.contains(&parent_key[..]) && FINAL_KEYS.contains(&key[..]) { continue; }
 
                     let mut current_fragment = context.fragment.clone();
                     current_fragment.push(key.clone());
 
-                    let is_schema = PROPERTY_KEYS.contains(&parent_key[..]) || !NON_SCHEMA_KEYS.contains(&key[..]);
+                    let is_subschema = !value.is_array() &&
+                        (PROPERTY_KEYS.contains(&parent_key[..]) || !NON_SCHEMA_KEYS.contains(&key[..]));
 
                     let mut context = WalkContext {
                         url: id.as_ref().unwrap_or(context.url),
+                        document: context.document,
                         fragment: current_fragment,
-                        scopes: context.scopes
+                        scopes: context.scopes,
+                        refs: context.refs,
+                        links: context.links,
+                        errors: context.errors,
+                        draft: context.draft,
+                        format_mode: context.format_mode,
+                        limits: context.limits,
+                        subschemas: context.subschemas,
+                        depth: context.depth + is_schema as usize
                     };
 
-                    let scheme = try!(Schema::compile_sub(
+                    let scheme = Schema::compile_sub(
                         value.clone(),
                         &mut context,
                         keywords,
-                        is_schema
-                    ));
+                        is_subschema
+                    );
 
-                    tree.insert(helpers::encode(key), scheme);
+                    tree.insert(helpers::encode(key), sync::Arc::new(scheme));
                 }
             } else if def.is_array() {
                 let array = def.as_array().unwrap();
+                let parent_key = &context.fragment[context.fragment.len() - 1];
 
                 for (idx, value) in array.iter().enumerate() {
-                    if !value.is_object() && !value.is_array() { continue; }
+                    if !keywords.is_subschema(value, SCHEMA_KEYS.contains(&parent_key[..])) { continue; }
 
                     let mut current_fragment = context.fragment.clone();
                     current_fragment.push(idx.to_string().clone());
 
                     let mut context = WalkContext {
                         url: id.as_ref().unwrap_or(context.url),
+                        document: context.document,
                         fragment: current_fragment,
-                        scopes: context.scopes
+                        scopes: context.scopes,
+                        refs: context.refs,
+                        links: context.links,
+                        errors: context.errors,
+                        draft: context.draft,
+                        format_mode: context.format_mode,
+                        limits: context.limits,
+                        subschemas: context.subschemas,
+                        depth: context.depth + is_schema as usize
                     };
 
-                    let scheme = try!(Schema::compile_sub(
+                    let scheme = Schema::compile_sub(
                         value.clone(),
                         &mut context,
                         keywords,
-                        true
-                    ));
+                        !value.is_array()
+                    );
 
-                    tree.insert(idx.to_string().clone(), scheme);
+                    tree.insert(idx.to_string().clone(), sync::Arc::new(scheme));
                 }
             }
 
@@ -270,28 +906,168 @@ impl Schema {
             context.scopes.insert(id.clone().unwrap().into_string(), context.fragment.clone());
         }
 
+        if is_schema {
+            for anchor in Schema::parse_anchors(&def, id.as_ref().unwrap_or(context.url), keywords.draft) {
+                context.scopes.insert(anchor.into_string(), context.fragment.clone());
+            }
+        }
+
         let validators = if is_schema && def.is_object() {
-            try!(Schema::compile_keywords(&def, context, keywords))
+            Schema::collect_refs(&def, id.as_ref().unwrap_or(context.url), context.refs);
+            Schema::compile_keywords(&def, context, keywords)
+        } else if is_schema && def.is_boolean() {
+            Schema::compile_boolean(&def, &context.fragment)
         } else {
             vec![]
         };
 
+        let error_message = if is_schema && def.is_object() {
+            error_message::compile(&def, context).unwrap_or_else(|error| {
+                context.errors.borrow_mut().push(error);
+                None
+            })
+        } else {
+            None
+        };
+
+        let dynamic_anchors = match id {
+            Some(ref id) if is_schema && def.is_object() => Schema::link_dynamic_anchors(&def, &tree, id, context),
+            _ => collections::HashMap::new()
+        };
+
+        let uses_evaluated = validators.iter().any(|validator| validator.uses_evaluated());
         let schema = Schema {
             id: id,
             schema: schema,
             original: def,
             tree: tree,
             validators: validators,
-            scopes: collections::HashMap::new()
+            scopes: collections::HashMap::new(),
+            refs: vec![],
+            links: vec![],
+            document: context.document.clone(),
+            fragment: context.fragment.clone(),
+            error_message: error_message,
+            uses_evaluated: uses_evaluated,
+            dynamic_anchors: dynamic_anchors
         };
 
-        Ok(schema)
+        schema
+    }
+
+    fn collect_refs(def: &Value, base: &url::Url, refs: &mut Vec<url::Url>) {
+        for key in ["$ref", "$recursiveRef", "$dynamicRef"].iter() {
+            let url = def.get(*key).and_then(|ref_| ref_.as_str()).and_then(|ref_| {
+                url::Url::options().base_url(Some(base)).parse(ref_).ok()
+            });
+
+            match url {
+                Some(url) => refs.push(url),
+                None => ()
+            }
+        }
+    }
+
+    fn parse_anchors(def: &Value, base: &url::Url, draft: super::Draft) -> Vec<url::Url> {
+        let mut keys = vec![];
+        if draft.allows_anchors() {
+            keys.push("$anchor");
+        }
+        if draft >= super::Draft::V2020_12 {
+            keys.push("$dynamicAnchor");
+        }
+
+        keys.iter().filter_map(|key| def.get(*key).and_then(|anchor| anchor.as_str())).map(|anchor| {
+            let mut url = base.clone();
+            url.set_fragment(Some(anchor));
+            url
+        }).collect()
+    }
+
+    /// Links the `$dynamicAnchor`s of the resource identified by `id`, including the
+    /// ones of its subschemas that aren't resources themselves.
+    fn link_dynamic_anchors(def: &Value, tree: &collections::BTreeMap<String, sync::Arc<Schema>>, id: &url::Url, context: &WalkContext)
+        -> collections::HashMap<String, Link>
+    {
+        let mut anchors = collections::HashMap::new();
+        if context.draft < super::Draft::V2020_12 {
+            return anchors
+        }
+
+        let mut names = vec![];
+        names.extend(def.get("$dynamicAnchor").and_then(|anchor| anchor.as_str()).map(|anchor| anchor.to_string()));
+        Schema::collect_dynamic_anchors(tree, &mut names);
+
+        for name in names {
+            let mut url = id.clone();
+            url.set_fragment(Some(&name));
+            anchors.insert(name, context.link(url));
+        }
+
+        anchors
+    }
+
+    fn collect_dynamic_anchors(tree: &collections::BTreeMap<String, sync::Arc<Schema>>, names: &mut Vec<String>) {
+        for schema in tree.values() {
+            // The anchors of a nested resource belong to it
+            if schema.id.is_some() { continue; }
+
+            names.extend(schema.dynamic_anchor().map(|anchor| anchor.to_string()));
+            Schema::collect_dynamic_anchors(&schema.tree, names);
+        }
+    }
+
+    fn compile_boolean(def: &Value, fragment: &[String]) -> validators::Validators {
+        if def.as_bool() == Some(false) {
+            vec![Box::new(validators::BooleanSchema {
+                fragment: fragment.to_vec()
+            })]
+        } else {
+            vec![]
+        }
+    }
+
+    /// Every reference made by the schema document, resolved against its base URI.
+    pub fn refs(&self) -> &[url::Url] {
+        &self.refs
+    }
+
+    /// Every link made by the validators of the schema document.
+    pub fn links(&self) -> &[Link] {
+        &self.links
+    }
+
+    /// The name declared by `$dynamicAnchor`.
+    pub fn dynamic_anchor(&self) -> Option<&str> {
+        self.original.get("$dynamicAnchor").and_then(|anchor| anchor.as_str())
+    }
+
+    /// The schema of the resource declared with `"$dynamicAnchor": name`.
+    pub fn dynamic_anchor_target(&self, name: &str) -> Option<sync::Arc<Schema>> {
+        self.dynamic_anchors.get(name).map(|link| link.get())
+    }
+
+    /// Whether the schema is declared with `"$recursiveAnchor": true`.
+    pub fn recursive_anchor(&self) -> bool {
+        self.original.get("$recursiveAnchor").and_then(|anchor| anchor.as_bool()).unwrap_or(false)
     }
 
     pub fn resolve(&self, id: &str) -> Option<&Schema> {
         let path = self.scopes.get(id);
         path.map(|path| {
             let mut schema = self;
+            for item in path.iter() {
+                schema = &**schema.tree.get(item).unwrap()
+            }
+            schema
+        })
+    }
+
+    /// Same as `resolve`, but keeps the shared handle of the found subschema.
+    pub fn resolve_shared<'a>(this: &'a sync::Arc<Schema>, id: &str) -> Option<&'a sync::Arc<Schema>> {
+        let path = this.scopes.get(id);
+        path.map(|path| {
+            let mut schema = this;
             for item in path.iter() {
                 schema = schema.tree.get(item).unwrap()
             }
@@ -304,6 +1080,22 @@ impl Schema {
 
         let parts = fragment[1..].split("/");
         let mut schema = self;
+        for part in parts {
+            match schema.tree.get(part) {
+                Some(sch) => schema = &**sch,
+                None => return None
+            }
+        }
+
+        Some(schema)
+    }
+
+    /// Same as `resolve_fragment`, but keeps the shared handle of the found subschema.
+    pub fn resolve_fragment_shared<'a>(this: &'a sync::Arc<Schema>, fragment: &str) -> Option<&'a sync::Arc<Schema>> {
+        assert!(fragment.starts_with("/"), "Can't resolve id fragments");
+
+        let parts = fragment[1..].split("/");
+        let mut schema = this;
         for part in parts {
             match schema.tree.get(part) {
                 Some(sch) => schema = sch,
@@ -316,15 +1108,113 @@ impl Schema {
 }
 
 impl Schema {
-    fn validate_in_scope(&self, data: &Value, path: &str, scope: &scope::Scope) -> validators::ValidationState {
+    pub fn validate_in_scope(&self, data: &Value, path: &str, scope: &scope::Scope, dynamic_scope: &validators::DynamicScope) -> validators::ValidationState {
         let mut state = validators::ValidationState::new();
 
+        // Keyword locations are relative to the schema the validation starts at.
+        let document_scope;
+        let dynamic_scope = if dynamic_scope.document().is_none() {
+            document_scope = dynamic_scope.follow(&[], &self.document, &self.fragment);
+            &document_scope
+        } else {
+            dynamic_scope
+        };
+
+        let resource_scope;
+        let dynamic_scope = match self.id {
+            Some(_) => {
+                resource_scope = dynamic_scope.push(self);
+                &resource_scope
+            },
+            None => dynamic_scope
+        };
+
+        // The applicators of a schema with `unevaluated*` keywords evaluate every subschema
+        let evaluated_scope;
+        let dynamic_scope = if self.uses_evaluated && !dynamic_scope.tracks_evaluated() {
+            evaluated_scope = dynamic_scope.track_evaluated();
+            &evaluated_scope
+        } else {
+            dynamic_scope
+        };
+
+        // The errors found before this schema count towards the maximum
+        let dynamic_scope = &dynamic_scope.resume();
+
         for validator in self.validators.iter() {
-            state.append(validator.validate(data, path, scope))
+            if dynamic_scope.reached_max_errors(&state) { break; }
+
+            let validator_state = validator.validate_with_state(data, path, scope, &dynamic_scope.resume(), &state);
+            state.append(validator_state)
+        }
+
+        if let Some(ref error_message) = self.error_message {
+            if !state.is_valid() && dynamic_scope.builds_errors() {
+                error_message.apply(&mut state, data, path, &self.fragment, dynamic_scope);
+            }
+        }
+
+        dynamic_scope.count_errors(&state);
+
+        if dynamic_scope.records_evaluations() {
+            let evaluations = mem::replace(&mut state.evaluations, vec![]);
+            state.evaluations.push(validators::Evaluation {
+                path: path.to_string(),
+                location: dynamic_scope.locate(&self.fragment),
+                valid: state.is_valid(),
+                evaluations: evaluations
+            });
+        }
+
+        if dynamic_scope.collects_annotations() {
+            // Annotations of a schema that failed are dropped along with the ones of its subschemas
+            if state.is_valid() {
+                self.collect_annotations(path, dynamic_scope, &mut state);
+            } else {
+                state.annotations.clear();
+            }
         }
 
         state
     }
+
+    fn collect_annotations(&self, path: &str, dynamic_scope: &validators::DynamicScope, state: &mut validators::ValidationState) {
+        for keyword in ANNOTATION_KEYS.iter() {
+            if let Some(value) = self.original.get(*keyword) {
+                state.annotations.push(validators::Annotation {
+                    path: path.to_string(),
+                    keyword: keyword.to_string(),
+                    location: dynamic_scope.locate_keyword(&self.fragment, keyword),
+                    value: value.clone()
+                });
+            }
+        }
+    }
+
+    /// Validates the schema as the target of the reference `keyword` of the schema at
+    /// `fragment`, so the errors are located through the reference.
+    pub fn validate_reference(&self, data: &Value, path: &str, scope: &scope::Scope, dynamic_scope: &validators::DynamicScope, fragment: &[String], keyword: &str) -> validators::ValidationState {
+        if let Some(limit) = scope.limits().reference_depth {
+            if dynamic_scope.reference_depth() >= limit {
+                let mut state = validators::ValidationState::new();
+                if dynamic_scope.builds_errors() {
+                    state.errors.push(Box::new(errors::ReferenceDepth {
+                        path: path.to_string(),
+                        fragment: fragment.to_vec(),
+                        location: dynamic_scope.locate_keyword(fragment, keyword),
+                        limit: limit as u64
+                    }));
+                } else {
+                    state.errors.push(Box::new(errors::Invalid));
+                }
+                return state
+            }
+        }
+
+        let mut keyword_location = dynamic_scope.keyword_location(fragment);
+        keyword_location.push(keyword.to_string());
+        self.validate_in_scope(data, path, scope, &dynamic_scope.follow(&keyword_location, &self.document, &self.fragment))
+    }
 }
 
 pub fn compile(def: Value, external_id: Option<url::Url>, settings: CompilationSettings) -> Result<Schema, SchemaError> {
@@ -335,3 +1225,205 @@ pub fn compile(def: Value, external_id: Option<url::Url>, settings: CompilationS
 fn schema_doesnt_compile_not_object() {
     assert!(Schema::compile(Value::Bool(true), None, CompilationSettings::new(&keywords::default(), true)).is_err());
 }
+
+#[test]
+fn schema_compiles_boolean_since_draft6() {
+    let keywords = keywords::draft6();
+    let schema = Schema::compile(Value::Bool(false), None, CompilationSettings::with_draft(&keywords, true, super::Draft::V6)).ok().unwrap();
+    assert!(!schema.validate_in_scope(&Value::Null, "", &scope::Scope::new(), &validators::DynamicScope::new()).is_valid());
+
+    let schema = Schema::compile(Value::Bool(true), None, CompilationSettings::with_draft(&keywords, true, super::Draft::V6)).ok().unwrap();
+    assert!(schema.validate_in_scope(&Value::Null, "", &scope::Scope::new(), &validators::DynamicScope::new()).is_valid());
+}
+
+#[cfg(test)] use jsonway;
+
+#[test]
+fn annotations_of_valid_schemas() {
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(jsonway::object(|schema| {
+        schema.set("title", "Order".to_string());
+        schema.object("properties", |props| {
+            props.object("id", |id| {
+                id.set("readOnly", true);
+                id.array("anyOf", |any_of| {
+                    any_of.object(|s| {
+                        s.set("type", "string".to_string());
+                        s.set("format", "uuid".to_string());
+                    });
+                    any_of.object(|s| {
+                        s.set("type", "integer".to_string());
+                        s.set("description", "Legacy numeric id".to_string());
+                    });
+                });
+            });
+            props.object("note", |note| note.set("default", "".to_string()));
+        });
+    }).unwrap(), false).ok().unwrap();
+
+    let state = schema.annotate(&jsonway::object(|obj| obj.set("id", "2f0c6c5e-3c1f-4e1b-9a7b-0d7c1e6f2a11".to_string())).unwrap());
+    assert!(state.is_valid());
+
+    let mut annotations = state.annotations.iter().map(|annotation| {
+        (annotation.path.as_ref(), annotation.location.path.join("/"), annotation.value.clone())
+    }).collect::<Vec<(&str, String, Value)>>();
+    annotations.sort_by(|a, b| a.1.cmp(&b.1));
+
+    assert_eq!(annotations, vec![
+        ("/id", "properties/id/anyOf/0/format".to_string(), Value::String("uuid".to_string())),
+        ("/id", "properties/id/readOnly".to_string(), Value::Bool(true)),
+        ("", "title".to_string(), Value::String("Order".to_string()))
+    ]);
+    assert_eq!(state.annotations_at("/id").len(), 2);
+
+    // Plain validation doesn't collect anything
+    assert_eq!(schema.validate(&jsonway::object(|obj| obj.set("id", "2f0c6c5e-3c1f-4e1b-9a7b-0d7c1e6f2a11".to_string())).unwrap()).annotations.len(), 0);
+}
+
+#[test]
+fn fail_fast_validation() {
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(jsonway::object(|schema| {
+        schema.object("properties", |props| {
+            props.object("a", |a| a.set("type", "string".to_string()));
+            props.object("b", |b| b.set("type", "string".to_string()));
+            props.object("c", |c| {
+                c.array("anyOf", |any_of| {
+                    any_of.object(|s| s.set("type", "string".to_string()));
+                    any_of.object(|s| s.set("type", "boolean".to_string()));
+                });
+            });
+        });
+        schema.array("required", |required| required.push("d".to_string()));
+    }).unwrap(), true).ok().unwrap();
+
+    let invalid = jsonway::object(|obj| {
+        obj.set("a", 1);
+        obj.set("b", 2);
+        obj.set("c", 3);
+    }).unwrap();
+
+    assert_eq!(schema.validate(&invalid).errors.len(), 4);
+    assert!(!schema.is_valid(&invalid));
+    assert!(schema.is_valid(&jsonway::object(|obj| obj.set("d", 1)).unwrap()));
+    assert_eq!(schema.validate_with_max_errors(&invalid, 2).errors.len(), 2);
+    assert_eq!(schema.validate_with_max_errors(&invalid, 10).errors.len(), 4);
+
+    // No errors are built when only the validity matters
+    let found = cell::Cell::new(0);
+    let any_of = schema.validate_in_dynamic(&jsonway::object(|obj| {
+        obj.set("c", 3);
+        obj.set("d", 4);
+    }).unwrap(), "", &validators::DynamicScope::flag(&found));
+    assert_eq!(any_of.errors.len(), 1);
+    assert!(any_of.errors[0].downcast::<super::errors::Invalid>().is_some());
+
+    // The maximum is shared by all the subschemas
+    let items = scope.compile_and_return(::serde_json::from_str(r#"{
+        "items": { "required": ["a", "b"] }
+    }"#).unwrap(), true).ok().unwrap();
+    let invalid = ::serde_json::from_str(r#"[{}, {}, {}]"#).unwrap();
+
+    assert_eq!(items.validate(&invalid).errors.len(), 6);
+    assert_eq!(items.validate_with_max_errors(&invalid, 3).errors.len(), 3);
+    assert!(!items.is_valid(&invalid));
+}
+
+#[test]
+fn compile_errors_are_collected() {
+    let mut scope = scope::Scope::new();
+    let error = scope.compile(::serde_json::from_str(r#"{
+        "id": "http://example.com/schema",
+        "properties": {
+            "a/b": { "minimum": "5" },
+            "c": { "type": "text", "unknown": 1 }
+        }
+    }"#).unwrap(), true).err().unwrap();
+
+    let messages = error.errors().iter().map(|error| error.to_string()).collect::<Vec<String>>();
+    assert_eq!(messages, vec![
+        "http://example.com/schema#/properties/a~1b: the `minimum/maximum` value must be a number",
+        "http://example.com/schema#/properties/c: String values MUST be one of the seven primitive types defined by the core specification. Unknown type: text",
+        "http://example.com/schema#/properties/c/unknown: unknown keyword `unknown`"
+    ]);
+    assert!(error.to_string().starts_with("3 errors in the schema\n"));
+
+    scope.compile(::serde_json::from_str(r#"{"id": "http://example.com/schema"}"#).unwrap(), true).ok().unwrap();
+    let error = scope.compile(::serde_json::from_str(r#"{"id": "http://example.com/schema"}"#).unwrap(), true).err().unwrap();
+    assert_eq!(error.url().unwrap().as_str(), "http://example.com/schema");
+    assert_eq!(error.pointer(), Some(""));
+    assert_eq!(error.to_string(), "http://example.com/schema: the scope already has a schema with this id");
+
+    // It can be used where any error is expected
+    let boxed: Box<error::Error + Send + Sync> = Box::new(error);
+    assert!(boxed.source().is_none());
+}
+
+#[test]
+fn compile_limits() {
+    let mut scope = scope::Scope::new();
+    scope.set_limits(Limits { regex_size: Some(1000), depth: Some(3), subschemas: Some(4), ..Limits::default() });
+
+    let messages = |def: &str, scope: &mut scope::Scope| {
+        let error = scope.compile(::serde_json::from_str(def).unwrap(), true).err().unwrap();
+        error.errors().iter().map(|error| error.to_string()).collect::<Vec<String>>()
+    };
+
+    assert_eq!(messages(r#"{"id": "http://example.com/a", "pattern": "^\\w{100}$"}"#, &mut scope), vec![
+        "http://example.com/a: the regular expression `^\\w{100}$` is bigger than the limit of 1000 bytes once compiled"
+    ]);
+    assert_eq!(messages(r#"{"id": "http://example.com/b", "patternProperties": {"^\\w{100}$": {}}}"#, &mut scope), vec![
+        "http://example.com/b: the regular expression `^\\w{100}$` is bigger than the limit of 1000 bytes once compiled"
+    ]);
+    assert_eq!(messages(r#"{"id": "http://example.com/c", "not": {"not": {"items": {"not": {"type": "string"}}}}}"#, &mut scope), vec![
+        "http://example.com/c#/not/not/items/not: the subschema is nested deeper than the limit of 3"
+    ]);
+    assert_eq!(messages(r#"{"id": "http://example.com/d", "allOf": [{}, {}, {}, {}, {}, {}]}"#, &mut scope), vec![
+        "http://example.com/d#/allOf/4: the document has more than 4 subschemas"
+    ]);
+
+    assert!(scope.compile(::serde_json::from_str(r#"{"allOf": [{"pattern": "^[a-z]+$"}, {}]}"#).unwrap(), true).is_ok());
+
+    // The depth counts schemas rather than keywords
+    assert!(scope.compile(::serde_json::from_str(r#"{"properties": {"a": {"properties": {"b": {"items": {}}}}}}"#).unwrap(), true).is_ok());
+}
+
+#[test]
+fn boolean_keyword_values_arent_subschemas() {
+    let mut scope = scope::Scope::new();
+    scope.set_limits(Limits { subschemas: Some(1), ..Limits::default() });
+
+    let schema = scope.compile_and_return(::serde_json::from_str(r#"{
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "uniqueItems": true,
+        "readOnly": true,
+        "items": false
+    }"#).unwrap(), true).ok().unwrap();
+    assert!(schema.validate(&::serde_json::from_str("[]").unwrap()).is_valid());
+    assert!(!schema.validate(&::serde_json::from_str("[1]").unwrap()).is_valid());
+}
+
+#[test]
+fn reference_depth_limit() {
+    let mut scope = scope::Scope::new();
+    scope.set_limits(Limits { reference_depth: Some(10), ..Limits::default() });
+
+    let schema = scope.compile_and_return(::serde_json::from_str(r##"{"$ref": "#"}"##).unwrap(), true).ok().unwrap();
+    let state = schema.validate(&Value::Null);
+    assert!(!state.is_valid());
+    assert_eq!(state.errors[0].get_code(), "reference_depth");
+    assert_eq!(state.errors[0].get_keyword_location().unwrap().len(), 11);
+
+    // Recursive schemas work up to the limit
+    let schema = scope.compile_and_return(::serde_json::from_str(r##"{
+        "properties": { "child": { "$ref": "#" } }
+    }"##).unwrap(), true).ok().unwrap();
+    assert!(schema.validate(&::serde_json::from_str(r#"{"child": {"child": {}}}"#).unwrap()).is_valid());
+    assert!(!schema.validate(&::serde_json::from_str(&format!("{}{}", r#"{"child": "#.repeat(11), "{}".to_string() + &"}".repeat(11))).unwrap()).is_valid());
+
+    // Loops are stopped by default
+    let mut scope = scope::Scope::new();
+    let schema = scope.compile_and_return(::serde_json::from_str(r##"{"$ref": "#"}"##).unwrap(), true).ok().unwrap();
+    let state = schema.validate(&Value::Null);
+    assert_eq!(state.errors[0].get_code(), "reference_depth");
+}
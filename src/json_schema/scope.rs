@@ -1,120 +1,412 @@
 use url;
 use std::collections;
+use std::path;
+use std::sync;
 use serde_json::{Value};
 
 use super::schema;
 use super::keywords;
 use super::helpers;
+use super::loader;
+use super::meta_schema;
+use super::Draft;
 
+const DRAFTS: [Draft; 5] = [Draft::V4, Draft::V6, Draft::V7, Draft::V2019_09, Draft::V2020_12];
+
+/// The compiled documents and the keywords are shared by the clones of the scope, and
+/// copied only when one of the clones changes them.
 #[allow(dead_code)]
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Scope {
-    keywords: keywords::KeywordMap,
-    schemes: collections::HashMap<String, schema::Schema>,
+    keywords: sync::Arc<collections::HashMap<Draft, keywords::KeywordMap>>,
+    schemes: sync::Arc<collections::HashMap<String, sync::Arc<schema::Schema>>>,
+    loaders: Vec<sync::Arc<loader::Loader>>,
+    default_draft: Draft,
+    meta_schemas: Option<sync::Arc<Scope>>,
+    /// The mode set for all the drafts, the one of the draft of each schema otherwise.
+    format_mode: Option<keywords::format::FormatMode>,
+    limits: schema::Limits,
 }
 
 #[allow(dead_code)]
 impl Scope {
     pub fn new() -> Scope {
-        let mut scope = Scope {
-            keywords: keywords::default(),
-            schemes: collections::HashMap::new()
-        };
-
+        let mut scope = Scope::without_formats();
         scope.add_keyword(vec!["format"], keywords::format::Format::new());
         scope
     }
 
     pub fn without_formats() -> Scope {
         Scope {
-            keywords: keywords::default(),
-            schemes: collections::HashMap::new()
+            keywords: sync::Arc::new(DRAFTS.iter().map(|draft| (*draft, keywords::for_draft(*draft))).collect()),
+            schemes: sync::Arc::new(collections::HashMap::new()),
+            loaders: vec![],
+            default_draft: Draft::V4,
+            meta_schemas: None,
+            format_mode: None,
+            limits: schema::Limits::default()
         }
     }
 
     pub fn with_formats<F>(build_formats: F) -> Scope where F: FnOnce(&mut keywords::format::FormatBuilders) {
-        let mut scope = Scope {
-            keywords: keywords::default(),
-            schemes: collections::HashMap::new()
-        };
-
+        let mut scope = Scope::without_formats();
         scope.add_keyword(vec!["format"], keywords::format::Format::with(build_formats));
         scope
     }
 
+    /// Sets the dialect used for schemas that don't declare a known `$schema`.
+    pub fn set_default_draft(&mut self, draft: Draft) {
+        self.default_draft = draft;
+    }
+
+    /// Adds a loader for the referenced documents that aren't in the scope. Loaders
+    /// are asked in the order they were added, at compile time.
+    pub fn add_loader<T>(&mut self, loader: T) where T: loader::Loader + 'static {
+        self.loaders.push(sync::Arc::new(loader));
+    }
+
+    /// Validates every schema against its meta-schema before compiling it. Schemas
+    /// that don't match it are rejected with `SchemaError::Invalid`.
+    ///
+    /// The meta-schemas of the supported drafts are bundled. Schemas declaring
+    /// another `$schema` are validated against it when it's in the scope, and
+    /// against the meta-schema of the default draft otherwise.
+    pub fn set_validate_schemas(&mut self, validate: bool) {
+        self.meta_schemas = if validate { Some(sync::Arc::new(meta_schema::scope())) } else { None };
+    }
+
+    /// Sets how the `format` keyword of the schemas compiled from now on is handled,
+    /// whatever their draft. By default formats are asserted up to draft 7 and only
+    /// annotations since 2019-09, see `FormatMode::default_for`.
+    pub fn set_format_mode(&mut self, mode: keywords::format::FormatMode) {
+        self.format_mode = Some(mode);
+    }
+
+    /// Sets the limits on the resources used by the schemas compiled from now on and
+    /// by the validations, see `Limits`.
+    pub fn set_limits(&mut self, limits: schema::Limits) {
+        self.limits = limits;
+    }
+
+    pub fn limits(&self) -> schema::Limits {
+        self.limits
+    }
+
+    fn settings<'a>(&'a self, def: &Value, ban_unknown: bool) -> schema::CompilationSettings<'a> {
+        let draft = Draft::from_schema(def).unwrap_or(self.default_draft);
+        let mut settings = schema::CompilationSettings::with_draft(&self.keywords[&draft], ban_unknown, draft);
+        if let Some(format_mode) = self.format_mode {
+            settings.format_mode = format_mode;
+        }
+        settings.limits = self.limits;
+        settings
+    }
+
+    fn validate_schema(&self, def: &Value, id: Option<&url::Url>) -> Result<(), schema::SchemaError> {
+        let meta_schemas = match self.meta_schemas {
+            Some(ref meta_schemas) => meta_schemas,
+            None => return Ok(())
+        };
+
+        let declared = match Draft::from_schema(def) {
+            Some(_) => None,
+            None => def.get("$schema").and_then(|url| url.as_str()).and_then(|url| url::Url::parse(url).ok())
+        };
+
+        let draft = Draft::from_schema(def).unwrap_or(self.default_draft);
+        let (meta_schema, state) = match declared.as_ref().and_then(|url| self.resolve(url).map(|schema| (url, schema))) {
+            Some((url, schema)) => (url.clone(), schema.validate(def)),
+            None => {
+                let url = meta_schema::url(draft);
+                let state = meta_schemas.resolve(&url).unwrap().validate(def);
+                (url, state)
+            }
+        };
+
+        if state.is_valid() {
+            return Ok(())
+        }
+
+        let url = id.cloned()
+            .or_else(|| helpers::parse_url_key(draft.id_key(), def).ok().and_then(|url| url))
+            .unwrap_or_else(|| helpers::generate_id());
+
+        Err(schema::SchemaError::Invalid {
+            url: url,
+            pointer: "".to_string(),
+            meta_schema: meta_schema,
+            state: state
+        })
+    }
+
+    fn compile_schema(&self, def: Value, id: Option<url::Url>, ban_unknown: bool) -> Result<schema::Schema, schema::SchemaError> {
+        try!(self.validate_schema(&def, id.as_ref()));
+        let settings = self.settings(&def, ban_unknown);
+        schema::compile(def, id, settings)
+    }
+
     pub fn compile(&mut self, def: Value, ban_unknown: bool) -> Result<url::Url, schema::SchemaError> {
-        let schema = try!(schema::compile(def, None, schema::CompilationSettings::new(&self.keywords, ban_unknown)));
+        let schema = try!(self.compile_schema(def, None, ban_unknown));
         let id = schema.id.clone().unwrap();
-        try!(self.add(&id, schema));
+        try!(self.add_and_load(&id, schema, ban_unknown));
         Ok(id)
     }
 
     pub fn compile_with_id(&mut self, id: &url::Url, def: Value, ban_unknown: bool)
         -> Result<(), schema::SchemaError>
     {
-        let schema = try!(schema::compile(def, Some(id.clone()), schema::CompilationSettings::new(&self.keywords, ban_unknown)));
-        self.add(id, schema)
+        let schema = try!(self.compile_schema(def, Some(id.clone()), ban_unknown));
+        self.add_and_load(id, schema, ban_unknown)
     }
 
     pub fn compile_and_return<'a>(&'a mut self, def: Value, ban_unknown: bool)
         -> Result<schema::ScopedSchema<'a>, schema::SchemaError>
     {
-        let schema = try!(schema::compile(def, None, schema::CompilationSettings::new(&self.keywords, ban_unknown)));
-        self.add_and_return(schema.id.clone().as_ref().unwrap(), schema)
+        let id = try!(self.compile(def, ban_unknown));
+        Ok(self.resolve(&id).unwrap())
     }
 
     pub fn compile_and_return_with_id<'a>(&'a mut self, id: &url::Url, def: Value, ban_unknown: bool)
         -> Result<schema::ScopedSchema<'a>, schema::SchemaError>
     {
-        let schema = try!(schema::compile(def, Some(id.clone()), schema::CompilationSettings::new(&self.keywords, ban_unknown)));
-        self.add_and_return(id, schema)
+        try!(self.compile_with_id(id, def, ban_unknown));
+        Ok(self.resolve(id).unwrap())
     }
 
+    /// Same as `compile_and_return`, but returns an owned handle, see `resolve_owned`.
+    pub fn compile_and_return_owned(&mut self, def: Value, ban_unknown: bool)
+        -> Result<schema::CompiledSchema, schema::SchemaError>
+    {
+        let id = try!(self.compile(def, ban_unknown));
+        Ok(self.resolve_owned(&id).unwrap())
+    }
+
+    /// Compiles every `.json` file under the directory with the id its relative path
+    /// maps to under `base`, so `dir/user/profile.json` gets `<base>user/profile.json`.
+    ///
+    /// Either all the files are added or none, in which case the errors of every
+    /// failed file are returned.
+    pub fn compile_dir(&mut self, dir: &path::Path, base: &url::Url, ban_unknown: bool)
+        -> Result<Vec<url::Url>, Vec<loader::FileError>>
+    {
+        let mut errors = vec![];
+        let files = loader::list_dir(dir, base, &mut errors);
+
+        let mut compiled = vec![];
+        for (path, id) in files {
+            let result = loader::read_file(&path)
+                .map_err(|detail| schema::SchemaError::LoadError { url: id.clone(), pointer: "".to_string(), detail: detail })
+                .and_then(|def| self.compile_schema(def, Some(id.clone()), ban_unknown));
+
+            match result {
+                Ok(schema) => compiled.push((path, id, schema)),
+                Err(error) => errors.push(loader::FileError { path: path, error: error })
+            }
+        }
+
+        // Everything is added before following the references, so that the files
+        // can refer to each other.
+        let mut ids = vec![];
+        let mut refs = vec![];
+        for (path, id, schema) in compiled {
+            let schema_refs = schema.refs().to_vec();
+            match self.add(&id, schema) {
+                Ok(()) => {
+                    ids.push(id);
+                    refs.push((path, schema_refs));
+                },
+                Err(error) => errors.push(loader::FileError { path: path, error: error })
+            }
+        }
+
+        // The references are followed and linked even when some files failed, so
+        // that their errors are reported along with the others.
+        let mut added = ids.clone();
+        for &(ref path, ref schema_refs) in refs.iter() {
+            match self.load_refs(schema_refs.clone(), ban_unknown, &mut added) {
+                Ok(()) => (),
+                Err(error) => errors.push(loader::FileError { path: path.clone(), error: error })
+            }
+        }
+
+        for (&(ref path, _), id) in refs.iter().zip(ids.iter()) {
+            match self.link_added(&[id.clone()]) {
+                Ok(()) => (),
+                Err(error) => errors.push(loader::FileError { path: path.clone(), error: error })
+            }
+        }
+
+        // The documents provided by the loaders
+        match self.link_added(&added[ids.len()..]) {
+            Ok(()) => (),
+            Err(error) => errors.push(loader::FileError { path: dir.to_path_buf(), error: error })
+        }
+
+        if errors.len() > 0 {
+            self.remove_all(&added);
+            return Err(errors)
+        }
+
+        Ok(ids)
+    }
+
+    /// Registers the keyword in every supported dialect.
     pub fn add_keyword<T>(&mut self, keys: Vec<&'static str>, keyword: T) where T: keywords::Keyword + 'static {
-        keywords::decouple_keyword((keys, Box::new(keyword)), &mut self.keywords);
+        let consumer = sync::Arc::new(keywords::KeywordConsumer { keys: keys, keyword: Box::new(keyword) });
+        for map in sync::Arc::make_mut(&mut self.keywords).values_mut() {
+            keywords::insert_consumer(consumer.clone(), map);
+        }
     }
 
     fn add(&mut self, id: &url::Url, schema: schema::Schema) -> Result<(), schema::SchemaError> {
         let (id_str, fragment) = helpers::serialize_schema_path(id);
 
         match fragment {
-            Some(_) => return Err(schema::SchemaError::WrongId),
+            Some(_) => return Err(schema::SchemaError::WrongId { url: id.clone(), pointer: "".to_string() }),
             None => ()
         }
 
         if !self.schemes.contains_key(&id_str) {
-            self.schemes.insert(id_str, schema);
+            sync::Arc::make_mut(&mut self.schemes).insert(id_str, sync::Arc::new(schema));
             Ok(())
         } else {
-            Err(schema::SchemaError::IdConflicts)
+            Err(schema::SchemaError::IdConflicts { url: id.clone(), pointer: "".to_string() })
         }
     }
 
-    fn add_and_return<'a>(&'a mut self, id: &url::Url, schema: schema::Schema)
-        -> Result<schema::ScopedSchema<'a>, schema::SchemaError>
-    {
-        let (id_str, fragment) = helpers::serialize_schema_path(id);
+    /// Adds the schema along with the documents it references that the loaders
+    /// provide. Either everything is added or nothing.
+    fn add_and_load(&mut self, id: &url::Url, schema: schema::Schema, ban_unknown: bool) -> Result<(), schema::SchemaError> {
+        let refs = schema.refs().to_vec();
+        try!(self.add(id, schema));
 
-        match fragment {
-            Some(_) => return Err(schema::SchemaError::WrongId),
-            None => ()
+        let mut added = vec![id.clone()];
+        let mut result = self.load_refs(refs, ban_unknown, &mut added);
+        if result.is_ok() {
+            result = self.link_added(&added);
         }
 
-        if !self.schemes.contains_key(&id_str) {
-            self.schemes.insert(id_str.clone(), schema);
-            Ok(schema::ScopedSchema::new(self, self.schemes.get(&id_str).unwrap()))
-        } else {
-            Err(schema::SchemaError::IdConflicts)
+        if result.is_err() {
+            self.remove_all(&added);
+        }
+
+        result
+    }
+
+    /// Links the references of the added documents to the schemas they point at, so
+    /// that validation follows direct handles instead of looking urls up. Every
+    /// reference has to resolve in the scope.
+    fn link_added(&self, added: &[url::Url]) -> Result<(), schema::SchemaError> {
+        let mut errors = vec![];
+
+        for id in added.iter() {
+            let document = match self.schemes.get(&helpers::serialize_schema_path(id).0) {
+                Some(document) => document,
+                None => continue
+            };
+
+            for link in document.links() {
+                match self.resolve_shared(&link.url) {
+                    Some(schema) => link.set(schema),
+                    None => errors.push(link.unresolved())
+                }
+            }
+        }
+
+        match schema::SchemaError::join(errors) {
+            Some(error) => Err(error),
+            None => Ok(())
+        }
+    }
+
+    fn remove_all(&mut self, ids: &[url::Url]) {
+        for id in ids.iter() {
+            sync::Arc::make_mut(&mut self.schemes).remove(&helpers::serialize_schema_path(id).0);
         }
     }
 
+    fn load_refs(&mut self, mut refs: Vec<url::Url>, ban_unknown: bool, added: &mut Vec<url::Url>) -> Result<(), schema::SchemaError> {
+        if self.loaders.len() == 0 {
+            return Ok(())
+        }
+
+        let mut visited = collections::HashSet::new();
+
+        while let Some(url) = refs.pop() {
+            let mut document = url.clone();
+            document.set_fragment(None);
+
+            // Documents that are already in the scope break reference cycles
+            if self.resolve(&url).is_some() || self.schemes.contains_key(document.as_str()) {
+                continue;
+            }
+
+            if !visited.insert(document.to_string()) {
+                continue;
+            }
+
+            match try!(self.load(&document, ban_unknown)) {
+                Some(schema) => {
+                    refs.extend(schema.refs().iter().cloned());
+                    try!(self.add(&document, schema));
+                    added.push(document);
+                },
+                // Left unresolved, it's reported when the references are linked
+                None => ()
+            }
+        }
+
+        Ok(())
+    }
+
+    fn load(&self, url: &url::Url, ban_unknown: bool) -> Result<Option<schema::Schema>, schema::SchemaError> {
+        for loader in self.loaders.iter() {
+            match loader.load(url) {
+                Ok(Some(def)) => return self.compile_schema(def, Some(url.clone()), ban_unknown).map(Some),
+                Ok(None) => (),
+                Err(detail) => return Err(schema::SchemaError::LoadError {
+                    url: url.clone(),
+                    pointer: "".to_string(),
+                    detail: detail
+                })
+            }
+        }
+
+        Ok(None)
+    }
+
     pub fn resolve<'a>(&'a self, id: &url::Url) -> Option<schema::ScopedSchema<'a>> {
+        self.resolve_shared(id).map(|schema| schema::ScopedSchema::new(self, schema))
+    }
+
+    /// Same as `resolve`, but returns an owned handle that doesn't borrow the scope,
+    /// so more schemas can be compiled into it while the handle is kept.
+    ///
+    /// The handle shares the documents of the scope as it is now, without copying
+    /// them. They hold every schema its references are linked to, so schemas compiled
+    /// into the scope afterwards don't change it.
+    ///
+    /// The map of documents is copy-on-write: the next compilation while a handle is
+    /// alive copies the map (not the documents it points to). Taking a handle after
+    /// each of N compilations therefore costs O(N²) in total; compile the schemas
+    /// first, or resolve them from a finished scope with `resolve_compiled`.
+    pub fn resolve_owned(&self, id: &url::Url) -> Option<schema::CompiledSchema> {
+        self.resolve_shared(id).map(|schema| schema::CompiledSchema::new(sync::Arc::new(self.clone()), schema.clone()))
+    }
+
+    /// Same as `resolve`, but returns an owned handle that keeps the shared scope
+    /// alive and can be cloned into other threads.
+    pub fn resolve_compiled(scope: &sync::Arc<Scope>, id: &url::Url) -> Option<schema::CompiledSchema> {
+        scope.resolve_shared(id).map(|schema| schema::CompiledSchema::new(scope.clone(), schema.clone()))
+    }
+
+    fn resolve_shared<'a>(&'a self, id: &url::Url) -> Option<&'a sync::Arc<schema::Schema>> {
         let (schema_path, fragment) = helpers::serialize_schema_path(id);
 
         let schema = self.schemes.get(&schema_path).or_else(|| {
             // Searching for inline schema in O(N)
             for (_, schema) in self.schemes.iter() {
-                let internal_schema = schema.resolve(schema_path.as_ref());
+                let internal_schema = schema::Schema::resolve_shared(schema, schema_path.as_ref());
                 if internal_schema.is_some() {
                     return internal_schema
                 }
@@ -125,12 +417,8 @@ impl Scope {
 
         schema.and_then(|schema| {
             match fragment {
-                Some(ref fragment) => {
-                    schema.resolve_fragment(fragment.as_ref()).map(|schema| {
-                        schema::ScopedSchema::new(self, schema)
-                    })
-                },
-                None => Some(schema::ScopedSchema::new(self, schema))
+                Some(ref fragment) => schema::Schema::resolve_fragment_shared(schema, fragment.as_ref()),
+                None => Some(schema)
             }
         })
     }
@@ -138,6 +426,10 @@ impl Scope {
 
 #[cfg(test)]
 use jsonway;
+#[cfg(test)]
+use serde_json::to_value;
+#[cfg(test)]
+use std::thread;
 
 #[test]
 fn lookup() {
@@ -158,4 +450,210 @@ fn lookup() {
     assert!(scope.resolve(&url::Url::parse("http://example.com/schema#sub").ok().unwrap()).is_some());
     assert!(scope.resolve(&url::Url::parse("http://example.com/schema#sub/subschema").ok().unwrap()).is_some());
     assert!(scope.resolve(&url::Url::parse("http://example.com/schema#subschema").ok().unwrap()).is_some());
-}
\ No newline at end of file
+}
+
+#[test]
+fn lookup_draft6() {
+    let mut scope = Scope::new();
+    scope.set_default_draft(Draft::V6);
+
+    scope.compile(jsonway::object(|schema| {
+        schema.set("$id", "http://example.com/schema".to_string());
+        schema.object("definitions", |definitions| {
+            definitions.object("sub", |sub| {
+                sub.set("$id", "#sub".to_string());
+            });
+            definitions.set("forbidden", false);
+        });
+    }).unwrap(), false).ok().unwrap();
+
+    assert!(scope.resolve(&url::Url::parse("http://example.com/schema").ok().unwrap()).is_some());
+    assert!(scope.resolve(&url::Url::parse("http://example.com/schema#sub").ok().unwrap()).is_some());
+
+    let forbidden = scope.resolve(&url::Url::parse("http://example.com/schema#/definitions/forbidden").ok().unwrap()).unwrap();
+    assert!(!forbidden.validate(&Value::Null).is_valid());
+}
+
+#[test]
+fn compile_draft7_annotations() {
+    let mut scope = Scope::new();
+
+    scope.compile(jsonway::object(|schema| {
+        schema.set("$schema", "http://json-schema.org/draft-07/schema#".to_string());
+        schema.set("$comment", "Annotations are allowed with banned unknown keywords".to_string());
+        schema.object("properties", |properties| {
+            properties.object("id", |id| {
+                id.set("readOnly", true);
+            });
+            properties.object("password", |password| {
+                password.set("writeOnly", true);
+            });
+            properties.object("avatar", |avatar| {
+                avatar.set("contentMediaType", "image/png".to_string());
+                avatar.set("contentEncoding", "base64".to_string());
+            });
+        });
+    }).unwrap(), true).ok().unwrap();
+}
+
+#[test]
+fn lookup_anchor() {
+    let mut scope = Scope::new();
+
+    let schema = scope.compile_and_return(jsonway::object(|schema| {
+        schema.set("$schema", "https://json-schema.org/draft/2020-12/schema".to_string());
+        schema.set("$id", "http://example.com/schema".to_string());
+        schema.set("$ref", "#positive".to_string());
+        schema.object("$defs", |defs| {
+            defs.object("positive", |positive| {
+                positive.set("$anchor", "positive".to_string());
+                positive.set("minimum", 0);
+            });
+        });
+    }).unwrap(), true).ok().unwrap();
+
+    assert!(schema.validate(&to_value(1).unwrap()).is_strictly_valid());
+    assert!(!schema.validate(&to_value(-1).unwrap()).is_valid());
+}
+
+#[test]
+fn unresolved_refs_are_errors() {
+    let mut scope = Scope::new();
+
+    let root = jsonway::object(|schema| {
+        schema.set("id", "http://example.com/root".to_string());
+        schema.object("properties", |props| {
+            props.object("child", |child| {
+                child.set("$ref", "http://example.com/child#/definitions/positive".to_string());
+            });
+        });
+    }).unwrap();
+
+    match scope.compile(root.clone(), false) {
+        Err(schema::SchemaError::UnresolvedRef { ref url, ref pointer, ref reference }) => {
+            assert_eq!(url.as_str(), "http://example.com/root");
+            assert_eq!(pointer, "/properties/child");
+            assert_eq!(reference.as_str(), "http://example.com/child#/definitions/positive");
+        },
+        other => panic!("Expected an unresolved reference, got {:?}", other)
+    }
+    assert!(scope.resolve(&url::Url::parse("http://example.com/root").unwrap()).is_none());
+
+    scope.compile(jsonway::object(|schema| {
+        schema.set("id", "http://example.com/child".to_string());
+        schema.object("definitions", |defs| {
+            defs.object("positive", |positive| positive.set("minimum", 0));
+        });
+    }).unwrap(), false).ok().unwrap();
+
+    let id = scope.compile(root, false).ok().unwrap();
+    let schema = scope.resolve(&id).unwrap();
+    assert!(schema.validate(&jsonway::object(|obj| obj.set("child", 1)).unwrap()).is_strictly_valid());
+    assert!(!schema.validate(&jsonway::object(|obj| obj.set("child", -1)).unwrap()).is_valid());
+}
+
+#[test]
+fn validate_from_threads() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Scope>();
+    assert_send_sync::<schema::CompiledSchema>();
+
+    let mut scope = Scope::new();
+    let id = scope.compile(jsonway::object(|schema| {
+        schema.set("minimum", 0);
+    }).unwrap(), false).ok().unwrap();
+
+    let scope = sync::Arc::new(scope);
+    let schema = Scope::resolve_compiled(&scope, &id).unwrap();
+
+    let threads = (0..4).map(|value| {
+        let schema = schema.clone();
+        thread::spawn(move || schema.validate(&to_value(value - 2).unwrap()).is_valid())
+    }).collect::<Vec<thread::JoinHandle<bool>>>();
+
+    let results = threads.into_iter().map(|thread| thread.join().unwrap()).collect::<Vec<bool>>();
+    assert_eq!(results, vec![false, false, true, true]);
+}
+
+#[test]
+fn owned_schema_survives_additions() {
+    struct Api {
+        scope: Scope,
+        user: schema::CompiledSchema
+    }
+
+    let mut scope = Scope::new();
+    scope.compile(jsonway::object(|schema| {
+        schema.set("id", "http://example.com/address".to_string());
+        schema.array("required", |required| required.push("city".to_string()));
+    }).unwrap(), false).ok().unwrap();
+
+    let user = scope.compile_and_return_owned(jsonway::object(|schema| {
+        schema.object("properties", |props| {
+            props.object("address", |address| {
+                address.set("$ref", "http://example.com/address".to_string());
+            });
+        });
+    }).unwrap(), false).ok().unwrap();
+
+    let mut api = Api { scope: scope, user: user };
+
+    api.scope.compile(jsonway::object(|schema| {
+        schema.set("id", "http://example.com/company".to_string());
+        schema.set("type", "object".to_string());
+    }).unwrap(), false).ok().unwrap();
+
+    assert!(api.user.validate(&jsonway::object(|obj| {
+        obj.object("address", |address| address.set("city", "Berlin".to_string()));
+    }).unwrap()).is_strictly_valid());
+    assert!(!api.user.validate(&jsonway::object(|obj| {
+        obj.object("address", |_| {});
+    }).unwrap()).is_valid());
+
+    // The referenced schemas are kept by the handle, not by the scope
+    let user = api.user.clone();
+    drop(api);
+    assert!(!user.validate(&jsonway::object(|obj| {
+        obj.object("address", |_| {});
+    }).unwrap()).is_valid());
+}
+
+#[test]
+fn validate_schemas() {
+    let mut scope = Scope::new();
+    scope.set_validate_schemas(true);
+
+    let invalid = vec![
+        r#"{"properties": {"age": {"minimum": "5"}}}"#,
+        r#"{"$schema": "http://json-schema.org/draft-07/schema#", "required": "name"}"#,
+        r#"{"$schema": "https://json-schema.org/draft/2019-09/schema", "items": {"type": "text"}}"#,
+        r#"{"$schema": "https://json-schema.org/draft/2020-12/schema", "$defs": {"a": {"minLength": -1}}}"#
+    ];
+
+    for def in invalid {
+        match scope.compile(::serde_json::from_str(def).unwrap(), false) {
+            Err(schema::SchemaError::Invalid { meta_schema, state, .. }) => {
+                assert!(meta_schema.as_str().contains("json-schema.org"));
+                assert!(state.errors.len() > 0);
+            },
+            other => panic!("{} was not rejected: {:?}", def, other)
+        }
+    }
+
+    assert!(scope.compile(::serde_json::from_str(r##"{
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "$defs": {"a": {"minLength": 1}},
+        "prefixItems": [{"$ref": "#/$defs/a"}]
+    }"##).unwrap(), false).is_ok());
+
+    // Schemas can declare a meta-schema of the scope
+    scope.compile(::serde_json::from_str(r#"{
+        "id": "http://example.com/meta",
+        "required": ["title"]
+    }"#).unwrap(), false).ok().unwrap();
+    let custom = scope.compile(::serde_json::from_str(r#"{"$schema": "http://example.com/meta"}"#).unwrap(), false);
+    match custom {
+        Err(schema::SchemaError::Invalid { meta_schema, .. }) => assert_eq!(meta_schema.as_str(), "http://example.com/meta"),
+        other => panic!("{:?}", other)
+    }
+}
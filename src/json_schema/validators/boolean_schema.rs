@@ -0,0 +1,22 @@
+use serde_json::{Value};
+
+use super::super::errors;
+use super::super::scope;
+
+/// Validator of the `false` schema, which never matches.
+#[allow(missing_copy_implementations)]
+pub struct BooleanSchema {
+    pub fragment: Vec<String>,
+}
+
+impl super::Validator for BooleanSchema {
+    fn validate(&self, _val: &Value, path: &str, _scope: &scope::Scope, dynamic_scope: &super::DynamicScope) -> super::ValidationState {
+        val_error!(dynamic_scope,
+            errors::BooleanSchema {
+                fragment: self.fragment.clone(),
+                location: dynamic_scope.locate(&self.fragment),
+                path: path.to_string()
+            }
+        )
+    }
+}
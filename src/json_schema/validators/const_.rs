@@ -0,0 +1,29 @@
+use serde_json::{Value};
+
+use super::super::errors;
+use super::super::helpers;
+use super::super::scope;
+
+#[allow(missing_copy_implementations)]
+pub struct Const {
+    pub fragment: Vec<String>,
+    pub keyword: &'static str,
+    pub item: Value
+}
+
+impl super::Validator for Const {
+    fn validate(&self, val: &Value, path: &str, _scope: &scope::Scope, dynamic_scope: &super::DynamicScope) -> super::ValidationState {
+        if helpers::is_equal(val, &self.item) {
+            super::ValidationState::new()
+        } else {
+            val_error!(dynamic_scope,
+                errors::Const {
+                    fragment: self.fragment.clone(),
+                    location: dynamic_scope.locate_keyword(&self.fragment, self.keyword),
+                    path: path.to_string(),
+                    expected: self.item.clone()
+                }
+            )
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use serde_json::{Value};
+
+use super::super::errors;
+use super::super::schema;
+use super::super::scope;
+
+#[allow(missing_copy_implementations)]
+pub struct Contains {
+    pub fragment: Vec<String>,
+    pub link: schema::Link,
+    pub min_contains: Option<u64>,
+    pub max_contains: Option<u64>,
+    pub mark_evaluated: bool
+}
+
+impl super::Validator for Contains {
+    fn validate(&self, val: &Value, path: &str, scope: &scope::Scope, dynamic_scope: &super::DynamicScope) -> super::ValidationState {
+        let array = nonstrict_process!(val.as_array(), path);
+
+        let schema = self.link.get();
+        let mut state = super::ValidationState::new();
+
+        let min_contains = self.min_contains.unwrap_or(1);
+        let exhaustive = self.max_contains.is_some() || (self.mark_evaluated && dynamic_scope.tracks_evaluated());
+        let mut matched = 0;
+
+        for (idx, item) in array.iter().enumerate() {
+            let item_path = [path, idx.to_string().as_ref()].join("/");
+            // The errors of the items are dropped
+            dynamic_scope.count_errors(&state);
+            let mut item_state = schema.validate_in_scope(item, item_path.as_ref(), scope, dynamic_scope);
+
+            state.missing.extend(item_state.missing.clone());
+            state.evaluations.append(&mut item_state.evaluations);
+
+            if item_state.is_valid() {
+                matched += 1;
+                state.annotations.extend(item_state.annotations);
+
+                if self.mark_evaluated {
+                    state.evaluated.insert(idx.to_string());
+                }
+
+                if matched >= min_contains && !exhaustive {
+                    break;
+                }
+            }
+        }
+
+        if matched < min_contains {
+            if self.min_contains.is_some() {
+                state.errors.push(build_error!(dynamic_scope,
+                    errors::MinContains {
+                        fragment: self.fragment.clone(),
+                        location: dynamic_scope.locate_keyword(&self.fragment, "minContains"),
+                        path: path.to_string(),
+                        limit: min_contains,
+                        actual: matched
+                    }
+                ))
+            } else {
+                state.errors.push(build_error!(dynamic_scope,
+                    errors::Contains {
+                        fragment: self.fragment.clone(),
+                        location: dynamic_scope.locate_keyword(&self.fragment, "contains"),
+                        path: path.to_string()
+                    }
+                ))
+            }
+        }
+
+        if self.max_contains.map(|max_contains| matched > max_contains).unwrap_or(false) {
+            state.errors.push(build_error!(dynamic_scope,
+                errors::MaxContains {
+                    fragment: self.fragment.clone(),
+                    location: dynamic_scope.locate_keyword(&self.fragment, "maxContains"),
+                    path: path.to_string(),
+                    limit: self.max_contains.unwrap(),
+                    actual: matched
+                }
+            ))
+        }
+
+        state
+    }
+}
@@ -1,24 +1,25 @@
 use std::collections;
 use serde_json::{Value};
-use url;
 
 use super::super::errors;
+use super::super::schema;
 use super::super::scope;
 
 #[derive(Debug)]
 pub enum DepKind {
-    Schema(url::Url),
+    Schema(schema::Link),
     Property(Vec<String>)
 }
 
 #[allow(missing_copy_implementations)]
 pub struct Dependencies {
     pub fragment: Vec<String>,
+    pub keyword: &'static str,
     pub items: collections::HashMap<String, DepKind>
 }
 
 impl super::Validator for Dependencies {
-    fn validate(&self, object: &Value, path: &str, scope: &scope::Scope) -> super::ValidationState {
+    fn validate(&self, object: &Value, path: &str, scope: &scope::Scope, dynamic_scope: &super::DynamicScope) -> super::ValidationState {
         if !object.is_object() {
             return super::ValidationState::new()
         }
@@ -26,23 +27,22 @@ impl super::Validator for Dependencies {
         let mut state = super::ValidationState::new();
 
         for (key, dep) in self.items.iter() {
+            if dynamic_scope.reached_max_errors(&state) { break; }
+
             if object.get(&key).is_some() {
                 match dep {
-                    &DepKind::Schema(ref url) => {
-                        let schema = scope.resolve(url);
-                        if schema.is_some() {
-                            state.append(schema.unwrap().validate_in(object, path));
-                        } else {
-                            state.missing.push(url.clone())
-                        }
+                    &DepKind::Schema(ref link) => {
+                        state.append(link.get().validate_in_scope(object, path, scope, dynamic_scope));
                     },
                     &DepKind::Property(ref keys) => {
                         for key in keys.iter() {
                             if !object.get(&key).is_some() {
-                                state.errors.push(Box::new(
+                                state.errors.push(build_error!(dynamic_scope,
                                     errors::Required {
                                         fragment: self.fragment.clone(),
-                                        path: [path, key.as_ref()].join("/")
+                                        location: dynamic_scope.locate_keyword(&self.fragment, self.keyword),
+                                        path: super::property_path(path, key),
+                                        property: key.clone()
                                     }
                                 ))
                             }
@@ -1,31 +1,35 @@
 use serde_json::{Value};
 
 use super::super::errors;
+use super::super::helpers;
 use super::super::scope;
 
 #[allow(missing_copy_implementations)]
 pub struct Enum {
     pub fragment: Vec<String>,
+    pub keyword: &'static str,
     pub items: Vec<Value>
 }
 
 impl super::Validator for Enum {
-    fn validate(&self, val: &Value, path: &str, _scope: &scope::Scope) -> super::ValidationState {
+    fn validate(&self, val: &Value, path: &str, _scope: &scope::Scope, dynamic_scope: &super::DynamicScope) -> super::ValidationState {
         let mut state = super::ValidationState::new();
 
         let mut contains = false;
         for value in self.items.iter() {
-            if val == value {
+            if helpers::is_equal(val, value) {
                 contains = true;
                 break;
             }
         }
 
         if !contains {
-            state.errors.push(Box::new(
+            state.errors.push(build_error!(dynamic_scope,
                 errors::Enum {
                     fragment: self.fragment.clone(),
-                    path: path.to_string()
+                    location: dynamic_scope.locate_keyword(&self.fragment, self.keyword),
+                    path: path.to_string(),
+                    allowed: self.items.clone()
                 }
             ))
         }
@@ -2,27 +2,33 @@ use serde_json::{Value};
 use std::net;
 use uuid;
 use url;
+use url::idna;
+use regex;
 
+use super::super::ecma_regex;
 use super::super::errors;
 use super::super::scope;
 
 #[allow(missing_copy_implementations)]
 pub struct Ipv4 {
     pub fragment: Vec<String>,
+    pub keyword: &'static str,
 }
 
 impl super::Validator for Ipv4 {
-    fn validate(&self, val: &Value, path: &str, _scope: &scope::Scope) -> super::ValidationState {
+    fn validate(&self, val: &Value, path: &str, _scope: &scope::Scope, dynamic_scope: &super::DynamicScope) -> super::ValidationState {
         let string = nonstrict_process!(val.as_str(), path);
 
         match string.parse::<net::Ipv4Addr>() {
             Ok(_) => super::ValidationState::new(),
             Err(_) => {
-                val_error!(
+                val_error!(dynamic_scope,
                     errors::Format {
                         fragment: self.fragment.clone(),
+                        location: dynamic_scope.locate_keyword(&self.fragment, self.keyword),
                         path: path.to_string(),
-                        detail: "Wrong IP address".to_string()
+                        detail: "Wrong IP address".to_string(),
+                        format: "ipv4".to_string()
                     }
                 )
             }
@@ -33,20 +39,23 @@ impl super::Validator for Ipv4 {
 #[allow(missing_copy_implementations)]
 pub struct Ipv6 {
     pub fragment: Vec<String>,
+    pub keyword: &'static str,
 }
 
 impl super::Validator for Ipv6 {
-    fn validate(&self, val: &Value, path: &str, _scope: &scope::Scope) -> super::ValidationState {
+    fn validate(&self, val: &Value, path: &str, _scope: &scope::Scope, dynamic_scope: &super::DynamicScope) -> super::ValidationState {
         let string = nonstrict_process!(val.as_str(), path);
 
         match string.parse::<net::Ipv6Addr>() {
             Ok(_) => super::ValidationState::new(),
             Err(_) => {
-                val_error!(
+                val_error!(dynamic_scope,
                     errors::Format {
                         fragment: self.fragment.clone(),
+                        location: dynamic_scope.locate_keyword(&self.fragment, self.keyword),
                         path: path.to_string(),
-                        detail: "Wrong IP address".to_string()
+                        detail: "Wrong IP address".to_string(),
+                        format: "ipv6".to_string()
                     }
                 )
             }
@@ -57,20 +66,23 @@ impl super::Validator for Ipv6 {
 #[allow(missing_copy_implementations)]
 pub struct Uuid {
     pub fragment: Vec<String>,
+    pub keyword: &'static str,
 }
 
 impl super::Validator for Uuid {
-    fn validate(&self, val: &Value, path: &str, _scope: &scope::Scope) -> super::ValidationState {
+    fn validate(&self, val: &Value, path: &str, _scope: &scope::Scope, dynamic_scope: &super::DynamicScope) -> super::ValidationState {
         let string = nonstrict_process!(val.as_str(), path);
 
         match string.parse::<uuid::Uuid>() {
             Ok(_) => super::ValidationState::new(),
             Err(err) => {
-                val_error!(
+                val_error!(dynamic_scope,
                     errors::Format {
                         fragment: self.fragment.clone(),
+                        location: dynamic_scope.locate_keyword(&self.fragment, self.keyword),
                         path: path.to_string(),
-                        detail: format!("Malformed UUID: {:?}", err)
+                        detail: format!("Malformed UUID: {:?}", err),
+                        format: "uuid".to_string()
                     }
                 )
             }
@@ -81,23 +93,557 @@ impl super::Validator for Uuid {
 #[allow(missing_copy_implementations)]
 pub struct Uri {
     pub fragment: Vec<String>,
+    pub keyword: &'static str,
 }
 
 impl super::Validator for Uri {
-    fn validate(&self, val: &Value, path: &str, _scope: &scope::Scope) -> super::ValidationState {
+    fn validate(&self, val: &Value, path: &str, _scope: &scope::Scope, dynamic_scope: &super::DynamicScope) -> super::ValidationState {
         let string = nonstrict_process!(val.as_str(), path);
 
         match url::Url::parse(string) {
             Ok(_) => super::ValidationState::new(),
             Err(err) => {
-                val_error!(
+                val_error!(dynamic_scope,
                     errors::Format {
                         fragment: self.fragment.clone(),
+                        location: dynamic_scope.locate_keyword(&self.fragment, self.keyword),
                         path: path.to_string(),
-                        detail: format!("Malformed URI: {}", err)
+                        detail: format!("Malformed URI: {}", err),
+                        format: "uri".to_string()
                     }
                 )
             }
         }
     }
 }
+
+/// A validator of the format that accepts the strings `$check` returns true for.
+macro_rules! format_validator {
+    ($name:ident, $format:expr, $check:ident, $detail:expr) => {
+        #[allow(missing_copy_implementations)]
+        pub struct $name {
+            pub fragment: Vec<String>,
+            pub keyword: &'static str,
+        }
+
+        impl super::Validator for $name {
+            fn validate(&self, val: &Value, path: &str, _scope: &scope::Scope, dynamic_scope: &super::DynamicScope) -> super::ValidationState {
+                let string = nonstrict_process!(val.as_str(), path);
+
+                if $check(string) {
+                    super::ValidationState::new()
+                } else {
+                    val_error!(dynamic_scope,
+                        errors::Format {
+                            fragment: self.fragment.clone(),
+                            location: dynamic_scope.locate_keyword(&self.fragment, self.keyword),
+                            path: path.to_string(),
+                            detail: $detail.to_string(),
+                            format: $format.to_string()
+                        }
+                    )
+                }
+            }
+        }
+    }
+}
+
+format_validator!(Email, "email", is_email, "Malformed email address");
+format_validator!(IdnEmail, "idn-email", is_idn_email, "Malformed internationalized email address");
+format_validator!(Hostname, "hostname", is_hostname, "Malformed host name");
+format_validator!(IdnHostname, "idn-hostname", is_idn_hostname, "Malformed internationalized host name");
+format_validator!(DateTime, "date-time", is_date_time, "Malformed date and time");
+format_validator!(Date, "date", is_date, "Malformed date");
+format_validator!(Time, "time", is_time, "Malformed time");
+format_validator!(Duration, "duration", is_duration, "Malformed duration");
+format_validator!(UriReference, "uri-reference", is_uri_reference, "Malformed URI reference");
+format_validator!(Iri, "iri", is_iri, "Malformed IRI");
+format_validator!(IriReference, "iri-reference", is_iri_reference, "Malformed IRI reference");
+format_validator!(UriTemplate, "uri-template", is_uri_template, "Malformed URI template");
+format_validator!(JsonPointer, "json-pointer", is_json_pointer, "Malformed JSON pointer");
+format_validator!(RelativeJsonPointer, "relative-json-pointer", is_relative_json_pointer, "Malformed relative JSON pointer");
+format_validator!(Regex, "regex", is_regex, "Malformed regular expression");
+
+/// Mailbox of RFC 5321, section 4.1.2.
+pub fn is_email(string: &str) -> bool {
+    is_mailbox(string, false)
+}
+
+/// Mailbox of RFC 6531, section 3.3, which allows UTF-8 in the local part and
+/// internationalized domain names.
+pub fn is_idn_email(string: &str) -> bool {
+    is_mailbox(string, true)
+}
+
+fn is_mailbox(string: &str, idn: bool) -> bool {
+    let at = match string.rfind('@') {
+        Some(at) => at,
+        None => return false
+    };
+    let (local, domain) = (&string[..at], &string[at + 1..]);
+
+    let valid_local = if local.starts_with('"') {
+        is_quoted_string(local, idn)
+    } else {
+        local.split('.').all(|atom| atom.len() > 0 && atom.chars().all(|ch| is_atext(ch, idn)))
+    };
+
+    // The local part is limited to 64 octets (section 4.5.3.1.1)
+    let valid_domain = if domain.starts_with('[') {
+        is_address_literal(domain)
+    } else if idn {
+        is_idn_hostname(domain)
+    } else {
+        is_hostname(domain)
+    };
+
+    valid_local && local.len() <= 64 && valid_domain
+}
+
+fn is_atext(ch: char, idn: bool) -> bool {
+    ch.is_ascii_alphanumeric() || "!#$%&'*+-/=?^_`{|}~".contains(ch) || (idn && ch > '\u{7f}')
+}
+
+/// Quoted-string, where `qtextSMTP` is any printable character but `"` and `\`,
+/// which have to be escaped.
+fn is_quoted_string(string: &str, idn: bool) -> bool {
+    if string.len() < 2 || !string.ends_with('"') {
+        return false
+    }
+
+    let mut chars = string[1..string.len() - 1].chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => match chars.next() {
+                Some(escaped) if escaped >= ' ' && escaped <= '~' => (),
+                _ => return false
+            },
+            '"' => return false,
+            ' ' ..= '~' => (),
+            _ if idn && ch > '\u{7f}' => (),
+            _ => return false
+        }
+    }
+
+    true
+}
+
+/// IPv4 and IPv6 address literals, like `[127.0.0.1]` and `[IPv6:::1]`.
+fn is_address_literal(domain: &str) -> bool {
+    if domain.len() < 2 || !domain.starts_with('[') || !domain.ends_with(']') {
+        return false
+    }
+
+    let literal = &domain[1..domain.len() - 1];
+    match literal.get(..5) {
+        Some(tag) if tag.eq_ignore_ascii_case("IPv6:") => literal[5..].parse::<net::Ipv6Addr>().is_ok(),
+        _ => literal.parse::<net::Ipv4Addr>().is_ok()
+    }
+}
+
+/// Host names of RFC 1123, section 2.1, with the labels of RFC 5891, section
+/// 4.2.3.1: labels with `--` in the 3rd and 4th positions must be valid A-labels.
+pub fn is_hostname(string: &str) -> bool {
+    string.len() > 0 && string.len() <= 253 && string.split('.').all(is_hostname_label)
+}
+
+fn is_hostname_label(label: &str) -> bool {
+    label.len() > 0 && label.len() <= 63
+        && !label.starts_with('-') && !label.ends_with('-')
+        && label.bytes().all(|byte| byte.is_ascii_alphanumeric() || byte == b'-')
+        && (label.get(2..4) != Some("--") || is_a_label(label))
+}
+
+fn is_a_label(label: &str) -> bool {
+    match label.get(..4) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("xn--") => {
+            let (unicode, result) = idna::domain_to_unicode(label);
+            result.is_ok() && unicode != label && is_u_label(&unicode)
+        },
+        _ => false
+    }
+}
+
+/// Internationalized host names of RFC 5890, which are valid host names once
+/// converted to A-labels and follow the contextual rules of RFC 5892.
+pub fn is_idn_hostname(string: &str) -> bool {
+    if string.len() == 0 {
+        return false
+    }
+
+    let (unicode, result) = idna::domain_to_unicode(string);
+    if result.is_err() || !unicode.split('.').all(is_u_label) {
+        return false
+    }
+
+    match idna::domain_to_ascii(string) {
+        Ok(ascii) => is_hostname(&ascii),
+        Err(_) => false
+    }
+}
+
+/// Characters of the Virama combining class, which the joiners must follow.
+const VIRAMAS: [char; 57] = [
+    '\u{94d}', '\u{9cd}', '\u{a4d}', '\u{acd}', '\u{b4d}', '\u{bcd}', '\u{c4d}', '\u{ccd}', '\u{d3b}', '\u{d3c}',
+    '\u{d4d}', '\u{dca}', '\u{e3a}', '\u{eba}', '\u{f84}', '\u{1039}', '\u{103a}', '\u{1714}', '\u{1734}', '\u{17d2}',
+    '\u{1a60}', '\u{1b44}', '\u{1baa}', '\u{1bab}', '\u{1bf2}', '\u{1bf3}', '\u{2d7f}', '\u{a806}', '\u{a8c4}', '\u{a953}',
+    '\u{a9c0}', '\u{aaf6}', '\u{abed}', '\u{10a3f}', '\u{11046}', '\u{1107f}', '\u{110b9}', '\u{11133}', '\u{11134}', '\u{111c0}',
+    '\u{11235}', '\u{112ea}', '\u{1134d}', '\u{11442}', '\u{114c2}', '\u{115bf}', '\u{1163f}', '\u{116b6}', '\u{1172b}', '\u{11839}',
+    '\u{119e0}', '\u{11a34}', '\u{11a47}', '\u{11a99}', '\u{11c3f}', '\u{11d44}', '\u{11d97}'
+];
+
+/// Labels can't have `--` in the 3rd and 4th positions once decoded either, and
+/// follow the CONTEXTJ and CONTEXTO rules of RFC 5892, appendix A.
+fn is_u_label(label: &str) -> bool {
+    if label.chars().skip(2).take(2).collect::<String>() == "--" {
+        return false
+    }
+
+    let chars: Vec<char> = label.chars().collect();
+    let is_greek = |ch: char| (ch >= '\u{370}' && ch <= '\u{3ff}') || (ch >= '\u{1f00}' && ch <= '\u{1fff}');
+    let is_hebrew = |ch: char| ch >= '\u{590}' && ch <= '\u{5ff}';
+    let is_japanese = |ch: char| (ch >= '\u{3040}' && ch <= '\u{30ff}' && ch != '\u{30fb}')
+        || (ch >= '\u{31f0}' && ch <= '\u{31ff}')
+        || (ch >= '\u{3400}' && ch <= '\u{4dbf}')
+        || (ch >= '\u{4e00}' && ch <= '\u{9fff}')
+        || (ch >= '\u{f900}' && ch <= '\u{faff}')
+        || (ch >= '\u{20000}' && ch <= '\u{2fa1f}');
+
+    for (idx, &ch) in chars.iter().enumerate() {
+        let before = if idx > 0 { chars.get(idx - 1).cloned() } else { None };
+        let after = chars.get(idx + 1).cloned();
+
+        let valid = match ch {
+            // ZERO WIDTH NON-JOINER and ZERO WIDTH JOINER
+            '\u{200c}' | '\u{200d}' => before.map(|before| VIRAMAS.contains(&before)).unwrap_or(false),
+            // MIDDLE DOT
+            '\u{b7}' => before == Some('l') && after == Some('l'),
+            // GREEK LOWER NUMERAL SIGN (KERAIA)
+            '\u{375}' => after.map(&is_greek).unwrap_or(false),
+            // HEBREW PUNCTUATION GERESH and GERSHAYIM
+            '\u{5f3}' | '\u{5f4}' => before.map(&is_hebrew).unwrap_or(false),
+            // KATAKANA MIDDLE DOT
+            '\u{30fb}' => chars.iter().any(|&ch| is_japanese(ch)),
+            _ => true
+        };
+
+        if !valid {
+            return false
+        }
+    }
+
+    // ARABIC-INDIC DIGITS can't be mixed with EXTENDED ARABIC-INDIC DIGITS
+    let arabic_indic = chars.iter().any(|&ch| ch >= '\u{660}' && ch <= '\u{669}');
+    let extended = chars.iter().any(|&ch| ch >= '\u{6f0}' && ch <= '\u{6f9}');
+    !(arabic_indic && extended)
+}
+
+fn parse_digits(string: &str) -> Option<i32> {
+    if string.len() > 0 && string.bytes().all(|byte| byte.is_ascii_digit()) {
+        string.parse().ok()
+    } else {
+        None
+    }
+}
+
+fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i32, month: i32) -> i32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31
+    }
+}
+
+/// The `date-time` of RFC 3339, section 5.6.
+pub fn is_date_time(string: &str) -> bool {
+    match string.find(|ch| ch == 'T' || ch == 't') {
+        Some(idx) => is_date(&string[..idx]) && is_time(&string[idx + 1..]),
+        None => false
+    }
+}
+
+/// The `full-date` of RFC 3339, section 5.6, which has to exist in the calendar.
+pub fn is_date(string: &str) -> bool {
+    if string.len() != 10 || !string.is_ascii() || &string[4..5] != "-" || &string[7..8] != "-" {
+        return false
+    }
+
+    match (parse_digits(&string[..4]), parse_digits(&string[5..7]), parse_digits(&string[8..])) {
+        (Some(year), Some(month), Some(day)) => month >= 1 && month <= 12 && day >= 1 && day <= days_in_month(year, month),
+        _ => false
+    }
+}
+
+/// The `full-time` of RFC 3339, section 5.6. Leap seconds are only valid at the
+/// end of the day in UTC.
+pub fn is_time(string: &str) -> bool {
+    if string.len() < 9 || !string.is_ascii() || &string[2..3] != ":" || &string[5..6] != ":" {
+        return false
+    }
+
+    let (hour, minute, second) = match (parse_digits(&string[..2]), parse_digits(&string[3..5]), parse_digits(&string[6..8])) {
+        (Some(hour), Some(minute), Some(second)) if hour <= 23 && minute <= 59 && second <= 60 => (hour, minute, second),
+        _ => return false
+    };
+
+    let mut offset = &string[8..];
+    if offset.starts_with('.') {
+        let digits = offset[1..].bytes().take_while(|byte| byte.is_ascii_digit()).count();
+        if digits == 0 {
+            return false
+        }
+        offset = &offset[digits + 1..];
+    }
+
+    let offset_minutes = if offset.eq_ignore_ascii_case("z") {
+        0
+    } else if offset.len() == 6 && &offset[3..4] == ":" {
+        let sign = match &offset[..1] {
+            "+" => 1,
+            "-" => -1,
+            _ => return false
+        };
+        match (parse_digits(&offset[1..3]), parse_digits(&offset[4..])) {
+            (Some(hours), Some(minutes)) if hours <= 23 && minutes <= 59 => sign * (hours * 60 + minutes),
+            _ => return false
+        }
+    } else {
+        return false
+    };
+
+    second < 60 || (hour * 60 + minute - offset_minutes).rem_euclid(24 * 60) == 23 * 60 + 59
+}
+
+lazy_static! {
+    // The `duration` of RFC 3339, appendix A
+    static ref DURATION_REGEX: regex::Regex = {
+        let time = r"T(?:[0-9]+H(?:[0-9]+M(?:[0-9]+S)?)?|[0-9]+M(?:[0-9]+S)?|[0-9]+S)";
+        let date = r"(?:[0-9]+Y(?:[0-9]+M(?:[0-9]+D)?)?|[0-9]+M(?:[0-9]+D)?|[0-9]+D)";
+        regex::Regex::new(&format!(r"^P(?:{date}(?:{time})?|{time}|[0-9]+W)$", date = date, time = time)).unwrap()
+    };
+}
+
+pub fn is_duration(string: &str) -> bool {
+    DURATION_REGEX.is_match(string)
+}
+
+/// URI references of RFC 3986, section 4.1.
+pub fn is_uri_reference(string: &str) -> bool {
+    is_reference(string, false, false)
+}
+
+/// IRIs of RFC 3987, section 2.2.
+pub fn is_iri(string: &str) -> bool {
+    is_reference(string, true, true)
+}
+
+/// IRI references of RFC 3987, section 2.2.
+pub fn is_iri_reference(string: &str) -> bool {
+    is_reference(string, true, false)
+}
+
+fn is_unreserved(ch: char) -> bool {
+    ch.is_ascii_alphanumeric() || "-._~".contains(ch)
+}
+
+fn is_sub_delim(ch: char) -> bool {
+    "!$&'()*+,;=".contains(ch)
+}
+
+fn is_ucschar(ch: char) -> bool {
+    let code = ch as u32;
+    (code >= 0xa0 && code < 0xe0000 && !(code >= 0xe000 && code <= 0xf8ff) && !(code >= 0xfdd0 && code <= 0xfdef) && code & 0xfffe != 0xfffe)
+        || (code >= 0xe1000 && code <= 0xefffd)
+}
+
+fn is_iprivate(ch: char) -> bool {
+    let code = ch as u32;
+    (code >= 0xe000 && code <= 0xf8ff) || (code >= 0xf0000 && code & 0xfffe != 0xfffe)
+}
+
+/// Unreserved characters, which IRIs extend with `ucschar`. Percent signs are
+/// checked separately.
+fn is_iunreserved(ch: char, iri: bool) -> bool {
+    is_unreserved(ch) || ch == '%' || (iri && is_ucschar(ch))
+}
+
+fn is_pchar(ch: char, iri: bool) -> bool {
+    is_iunreserved(ch, iri) || is_sub_delim(ch) || ch == ':' || ch == '@'
+}
+
+fn has_valid_percent_encodings(string: &str) -> bool {
+    let bytes = string.as_bytes();
+    bytes.iter().enumerate().all(|(idx, &byte)| {
+        byte != b'%' || (idx + 2 < bytes.len() && (bytes[idx + 1] as char).is_digit(16) && (bytes[idx + 2] as char).is_digit(16))
+    })
+}
+
+fn split_at_first(string: &str, separator: char) -> (&str, Option<&str>) {
+    match string.find(separator) {
+        Some(idx) => (&string[..idx], Some(&string[idx + 1..])),
+        None => (string, None)
+    }
+}
+
+fn is_reference(string: &str, iri: bool, absolute: bool) -> bool {
+    if !has_valid_percent_encodings(string) {
+        return false
+    }
+
+    let (rest, fragment) = split_at_first(string, '#');
+    let (rest, query) = split_at_first(rest, '?');
+
+    let valid_fragment = fragment.map(|fragment| fragment.chars().all(|ch| is_pchar(ch, iri) || ch == '/' || ch == '?')).unwrap_or(true);
+    let valid_query = query.map(|query| query.chars().all(|ch| is_pchar(ch, iri) || ch == '/' || ch == '?' || (iri && is_iprivate(ch)))).unwrap_or(true);
+    if !valid_fragment || !valid_query {
+        return false
+    }
+
+    // A colon before the first slash ends the scheme, relative references can't
+    // have one in their first segment.
+    let rest = match rest.find(|ch| ch == ':' || ch == '/') {
+        Some(idx) if &rest[idx..idx + 1] == ":" => {
+            if !is_scheme(&rest[..idx]) {
+                return false
+            }
+            &rest[idx + 1..]
+        },
+        _ if absolute => return false,
+        _ => rest
+    };
+
+    let path = if rest.starts_with("//") {
+        let end = rest[2..].find('/').map(|idx| idx + 2).unwrap_or(rest.len());
+        if !is_authority(&rest[2..end], iri) {
+            return false
+        }
+        &rest[end..]
+    } else {
+        rest
+    };
+
+    path.chars().all(|ch| is_pchar(ch, iri) || ch == '/')
+}
+
+fn is_scheme(scheme: &str) -> bool {
+    scheme.chars().next().map(|ch| ch.is_ascii_alphabetic()).unwrap_or(false)
+        && scheme.chars().all(|ch| ch.is_ascii_alphanumeric() || ch == '+' || ch == '-' || ch == '.')
+}
+
+fn is_authority(authority: &str, iri: bool) -> bool {
+    let (userinfo, host_port) = match authority.rfind('@') {
+        Some(at) => (&authority[..at], &authority[at + 1..]),
+        None => ("", authority)
+    };
+
+    if !userinfo.chars().all(|ch| is_iunreserved(ch, iri) || is_sub_delim(ch) || ch == ':') {
+        return false
+    }
+
+    let (valid_host, port) = if host_port.starts_with('[') {
+        match host_port.find(']') {
+            Some(end) => (is_ip_literal(&host_port[1..end]), &host_port[end + 1..]),
+            None => return false
+        }
+    } else {
+        let end = host_port.find(':').unwrap_or(host_port.len());
+        (host_port[..end].chars().all(|ch| is_iunreserved(ch, iri) || is_sub_delim(ch)), &host_port[end..])
+    };
+
+    valid_host && (port.len() == 0 || (port.starts_with(':') && port[1..].bytes().all(|byte| byte.is_ascii_digit())))
+}
+
+/// IPv6 addresses and IPvFuture.
+fn is_ip_literal(literal: &str) -> bool {
+    if literal.starts_with('v') || literal.starts_with('V') {
+        let (version, address) = split_at_first(&literal[1..], '.');
+        version.len() > 0 && version.chars().all(|ch| ch.is_digit(16)) && address.map(|address| {
+            address.len() > 0 && address.chars().all(|ch| is_unreserved(ch) || is_sub_delim(ch) || ch == ':')
+        }).unwrap_or(false)
+    } else {
+        literal.parse::<net::Ipv6Addr>().is_ok()
+    }
+}
+
+/// URI templates of RFC 6570, section 2.
+pub fn is_uri_template(string: &str) -> bool {
+    if !has_valid_percent_encodings(string) {
+        return false
+    }
+
+    let mut rest = string;
+    while let Some(ch) = rest.chars().next() {
+        if ch == '{' {
+            let end = match rest.find('}') {
+                Some(end) => end,
+                None => return false
+            };
+            if !is_template_expression(&rest[1..end]) {
+                return false
+            }
+            rest = &rest[end + 1..];
+        } else {
+            if ch <= ' ' || ch == '\u{7f}' || "\"'<>\\^`{|}".contains(ch) {
+                return false
+            }
+            rest = &rest[ch.len_utf8()..];
+        }
+    }
+
+    true
+}
+
+fn is_template_expression(expression: &str) -> bool {
+    let variables = match expression.chars().next() {
+        Some(operator) if "+#./;?&=,!@|".contains(operator) => &expression[1..],
+        _ => expression
+    };
+
+    variables.split(',').all(|variable| {
+        let (name, modifier) = match variable.find(|ch| ch == ':' || ch == '*') {
+            Some(idx) => (&variable[..idx], &variable[idx..]),
+            None => (variable, "")
+        };
+
+        let valid_name = name.len() > 0 && !name.starts_with('.') && !name.ends_with('.') && !name.contains("..")
+            && name.chars().all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '%' || ch == '.');
+        let valid_modifier = modifier == "" || modifier == "*" || (modifier.starts_with(':') && {
+            let length = &modifier[1..];
+            length.len() > 0 && length.len() <= 4 && !length.starts_with('0') && length.bytes().all(|byte| byte.is_ascii_digit())
+        });
+
+        valid_name && valid_modifier
+    })
+}
+
+/// JSON pointers of RFC 6901, section 3.
+pub fn is_json_pointer(string: &str) -> bool {
+    (string.len() == 0 || string.starts_with('/')) && string.match_indices('~').all(|(idx, _)| {
+        match string.get(idx + 1..idx + 2) {
+            Some("0") | Some("1") => true,
+            _ => false
+        }
+    })
+}
+
+/// Relative JSON pointers of draft-handrews-relative-json-pointer-01, section 3.
+pub fn is_relative_json_pointer(string: &str) -> bool {
+    let digits = string.bytes().take_while(|byte| byte.is_ascii_digit()).count();
+    let (prefix, pointer) = string.split_at(digits);
+
+    prefix.len() > 0 && (prefix == "0" || !prefix.starts_with('0')) && (pointer == "#" || is_json_pointer(pointer))
+}
+
+/// ECMA-262 regular expressions. The ones that can't be translated, like
+/// backreferences, are still valid.
+pub fn is_regex(string: &str) -> bool {
+    match ecma_regex::compile(string) {
+        Err(ecma_regex::RegexError::Syntax(_)) => false,
+        _ => true
+    }
+}
@@ -0,0 +1,37 @@
+use serde_json::{Value};
+
+use super::super::schema;
+use super::super::scope;
+
+#[allow(missing_copy_implementations)]
+pub struct If {
+    pub link: schema::Link,
+    pub then_link: Option<schema::Link>,
+    pub else_link: Option<schema::Link>
+}
+
+impl super::Validator for If {
+    fn validate(&self, val: &Value, path: &str, scope: &scope::Scope, dynamic_scope: &super::DynamicScope) -> super::ValidationState {
+        let mut state = super::ValidationState::new();
+
+        // Errors of the `if` schema are never reported, it only picks the branch.
+        let mut if_state = self.link.get().validate_in_scope(val, path, scope, dynamic_scope);
+        state.missing.extend(if_state.missing.clone());
+        state.evaluations.append(&mut if_state.evaluations);
+
+        let valid = if_state.is_valid();
+        if valid {
+            state.evaluated.extend(if_state.evaluated);
+            state.annotations.extend(if_state.annotations);
+        }
+
+        let branch = if valid { self.then_link.as_ref() } else { self.else_link.as_ref() };
+
+        if let Some(link) = branch {
+            dynamic_scope.count_errors(&state);
+            state.append(link.get().validate_in_scope(val, path, scope, dynamic_scope));
+        }
+
+        state
+    }
+}
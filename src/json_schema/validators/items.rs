@@ -1,91 +1,96 @@
 use serde_json::{Value};
 use std::cmp;
-use url;
 
 use super::super::errors;
+use super::super::schema;
 use super::super::scope;
 
 #[derive(Debug)]
 pub enum ItemsKind {
-    Schema(url::Url),
-    Array(Vec<url::Url>)
+    Schema(schema::Link),
+    Array(Vec<schema::Link>)
 }
 
 #[derive(Debug)]
 pub enum AdditionalKind {
     Boolean(bool),
-    Schema(url::Url)
+    Schema(schema::Link)
 }
 
 #[allow(missing_copy_implementations)]
 pub struct Items {
     pub fragment: Vec<String>,
+    pub keyword: &'static str,
     pub items: Option<ItemsKind>,
-    pub additional: Option<AdditionalKind>
+    pub additional: Option<AdditionalKind>,
+    /// Whether the evaluated items are recorded for `unevaluatedItems`.
+    pub mark_evaluated: bool
 }
 
 impl super::Validator for Items {
-    fn validate(&self, val: &Value, path: &str, scope: &scope::Scope) -> super::ValidationState {
+    fn validate(&self, val: &Value, path: &str, scope: &scope::Scope, dynamic_scope: &super::DynamicScope) -> super::ValidationState {
         let array = nonstrict_process!(val.as_array(), path);
 
         let mut state = super::ValidationState::new();
 
         match self.items {
-            Some(ItemsKind::Schema(ref url)) => {
+            Some(ItemsKind::Schema(ref link)) => {
                 // Just validate all items against the schema
 
-                let schema = scope.resolve(url);
-                if schema.is_some() {
-                    let schema = schema.unwrap();
-                    for (idx, item) in array.iter().enumerate() {
-                        let item_path = [path, idx.to_string().as_ref()].join("/");
-                        state.append(schema.validate_in(item, item_path.as_ref()));
+                let schema = link.get();
+                for (idx, item) in array.iter().enumerate() {
+                    if dynamic_scope.reached_max_errors(&state) { break; }
+                    let item_path = [path, idx.to_string().as_ref()].join("/");
+                    state.append_child(schema.validate_in_scope(item, item_path.as_ref(), scope, dynamic_scope));
+                    if self.mark_evaluated {
+                        state.evaluated.insert(idx.to_string());
                     }
-                } else {
-                    state.missing.push(url.clone());
                 }
             },
-            Some(ItemsKind::Array(ref urls)) => {
-                let min = cmp::min(urls.len(), array.len());
+            Some(ItemsKind::Array(ref links)) => {
+                let min = cmp::min(links.len(), array.len());
 
                 // Validate against schemas
                 for idx in 0..min {
-                    let schema = scope.resolve(&urls[idx]);
+                    if dynamic_scope.reached_max_errors(&state) { break; }
                     let item = &array[idx];
-
-                    if schema.is_some() {
-                        let item_path = [path, idx.to_string().as_ref()].join("/");
-                        state.append(schema.unwrap().validate_in(item, item_path.as_ref()))
-                    } else {
-                        state.missing.push(urls[idx].clone())
+                    let item_path = [path, idx.to_string().as_ref()].join("/");
+                    state.append_child(links[idx].get().validate_in_scope(item, item_path.as_ref(), scope, dynamic_scope));
+                    if self.mark_evaluated {
+                        state.evaluated.insert(idx.to_string());
                     }
                 }
 
                 // Validate agains additional items
-                if array.len() > urls.len() {
+                if array.len() > links.len() {
                     match self.additional {
                         Some(AdditionalKind::Boolean(allow)) if allow == false => {
-                            state.errors.push(Box::new(
+                            state.errors.push(build_error!(dynamic_scope,
                                 errors::Items {
                                     fragment: self.fragment.clone(),
+                                    location: dynamic_scope.locate_keyword(&self.fragment, self.keyword),
                                     path: path.to_string(),
                                     detail: "Additional items are not allowed".to_string()
                                 }
                             ))
                         },
-                        Some(AdditionalKind::Schema(ref url)) => {
-                            let schema = scope.resolve(url);
-                            if schema.is_some() {
-                                let schema = schema.unwrap();
-                                for (idx, item) in array[urls.len()..].iter().enumerate() {
-                                    let item_path = [path, idx.to_string().as_ref()].join("/");
-                                    state.append(schema.validate_in(item, item_path.as_ref()))
+                        Some(AdditionalKind::Schema(ref link)) => {
+                            let schema = link.get();
+                            for (idx, item) in array.iter().enumerate().skip(links.len()) {
+                                if dynamic_scope.reached_max_errors(&state) { break; }
+                                let item_path = [path, idx.to_string().as_ref()].join("/");
+                                state.append_child(schema.validate_in_scope(item, item_path.as_ref(), scope, dynamic_scope));
+                                if self.mark_evaluated {
+                                    state.evaluated.insert(idx.to_string());
                                 }
-                            } else {
-                                state.missing.push(url.clone())
                             }
                         },
-                        _ => ()
+                        Some(AdditionalKind::Boolean(_)) => {
+                            if self.mark_evaluated {
+                                state.evaluated.extend((links.len()..array.len()).map(|idx| idx.to_string()));
+                            }
+                        },
+                        None => ()
                     }
                 }
             }
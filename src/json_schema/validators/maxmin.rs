@@ -1,4 +1,4 @@
-use serde_json::{Value};
+use serde_json::{Number, Value};
 
 use super::super::errors;
 use super::super::scope;
@@ -6,13 +6,17 @@ use super::super::scope;
 #[allow(missing_copy_implementations)]
 pub struct Maximum {
     pub fragment: Vec<String>,
+    pub keyword: &'static str,
     pub number: f64,
+    /// The number as written in the schema, for the errors.
+    pub limit: Number,
     pub exclusive: bool
 }
 
 impl super::Validator for Maximum {
-    fn validate(&self, val: &Value, path: &str, _scope: &scope::Scope) -> super::ValidationState {
-        let number = nonstrict_process!(val.as_f64(), path);
+    fn validate(&self, val: &Value, path: &str, _scope: &scope::Scope, dynamic_scope: &super::DynamicScope) -> super::ValidationState {
+        let actual = nonstrict_process!(match *val { Value::Number(ref number) => Some(number), _ => None }, path);
+        let number = actual.as_f64().unwrap();
 
         let valid = if self.exclusive {
             number < self.number
@@ -23,10 +27,14 @@ impl super::Validator for Maximum {
         if valid {
             super::ValidationState::new()
         } else {
-            val_error!(
+            val_error!(dynamic_scope,
                 errors::Maximum {
                     fragment: self.fragment.clone(),
-                    path: path.to_string()
+                    location: dynamic_scope.locate_keyword(&self.fragment, self.keyword),
+                    path: path.to_string(),
+                    limit: self.limit.clone(),
+                    exclusive: self.exclusive,
+                    actual: actual.clone()
                 }
             )
         }
@@ -36,13 +44,17 @@ impl super::Validator for Maximum {
 #[allow(missing_copy_implementations)]
 pub struct Minimum {
     pub fragment: Vec<String>,
+    pub keyword: &'static str,
     pub number: f64,
+    /// The number as written in the schema, for the errors.
+    pub limit: Number,
     pub exclusive: bool
 }
 
 impl super::Validator for Minimum {
-    fn validate(&self, val: &Value, path: &str, _scope: &scope::Scope) -> super::ValidationState {
-        let number = nonstrict_process!(val.as_f64(), path);
+    fn validate(&self, val: &Value, path: &str, _scope: &scope::Scope, dynamic_scope: &super::DynamicScope) -> super::ValidationState {
+        let actual = nonstrict_process!(match *val { Value::Number(ref number) => Some(number), _ => None }, path);
+        let number = actual.as_f64().unwrap();
 
         let valid = if self.exclusive {
             number > self.number
@@ -53,10 +65,14 @@ impl super::Validator for Minimum {
         if valid {
             super::ValidationState::new()
         } else {
-            val_error!(
+            val_error!(dynamic_scope,
                 errors::Minimum {
                     fragment: self.fragment.clone(),
-                    path: path.to_string()
+                    location: dynamic_scope.locate_keyword(&self.fragment, self.keyword),
+                    path: path.to_string(),
+                    limit: self.limit.clone(),
+                    exclusive: self.exclusive,
+                    actual: actual.clone()
                 }
             )
         }
@@ -6,20 +6,24 @@ use super::super::scope;
 #[allow(missing_copy_implementations)]
 pub struct MaxItems {
     pub fragment: Vec<String>,
+    pub keyword: &'static str,
     pub length: u64
 }
 
 impl super::Validator for MaxItems {
-    fn validate(&self, val: &Value, path: &str, _scope: &scope::Scope) -> super::ValidationState {
+    fn validate(&self, val: &Value, path: &str, _scope: &scope::Scope, dynamic_scope: &super::DynamicScope) -> super::ValidationState {
         let array = nonstrict_process!(val.as_array(), path);
 
         if (array.len() as u64) <= self.length {
             super::ValidationState::new()
         } else {
-            val_error!(
+            val_error!(dynamic_scope,
                 errors::MaxItems {
                     fragment: self.fragment.clone(),
-                    path: path.to_string()
+                    location: dynamic_scope.locate_keyword(&self.fragment, self.keyword),
+                    path: path.to_string(),
+                    limit: self.length,
+                    actual: array.len() as u64
                 }
             )
         }
@@ -29,20 +33,24 @@ impl super::Validator for MaxItems {
 #[allow(missing_copy_implementations)]
 pub struct MinItems {
     pub fragment: Vec<String>,
+    pub keyword: &'static str,
     pub length: u64
 }
 
 impl super::Validator for MinItems {
-    fn validate(&self, val: &Value, path: &str, _scope: &scope::Scope) -> super::ValidationState {
+    fn validate(&self, val: &Value, path: &str, _scope: &scope::Scope, dynamic_scope: &super::DynamicScope) -> super::ValidationState {
         let array = nonstrict_process!(val.as_array(), path);
 
         if (array.len() as u64) >= self.length {
             super::ValidationState::new()
         } else {
-            val_error!(
+            val_error!(dynamic_scope,
                 errors::MinItems {
                     fragment: self.fragment.clone(),
-                    path: path.to_string()
+                    location: dynamic_scope.locate_keyword(&self.fragment, self.keyword),
+                    path: path.to_string(),
+                    limit: self.length,
+                    actual: array.len() as u64
                 }
             )
         }
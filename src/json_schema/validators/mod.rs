@@ -1,9 +1,15 @@
 use serde_json::{Value, to_value};
 use serde::{Serialize, Serializer};
 use std::fmt;
+use std::collections;
+use std::cell;
 use url;
 
 use super::scope;
+use super::schema;
+use super::errors;
+use super::super::common::error;
+use super::super::common::catalog;
 
 #[macro_export]
 macro_rules! strict_process {
@@ -33,13 +39,39 @@ macro_rules! nonstrict_process {
     }}
 }
 
+/// Boxes the error, unless the validation only checks whether the instance is
+/// valid, in which case the error isn't built at all.
+macro_rules! build_error {
+    ($dynamic_scope:expr, $err:expr) => (
+        if $dynamic_scope.builds_errors() {
+            Box::new($err) as Box<$crate::common::error::ValicoError>
+        } else {
+            Box::new($crate::json_schema::errors::Invalid) as Box<$crate::common::error::ValicoError>
+        }
+    )
+}
+
 macro_rules! val_error{
+    ($dynamic_scope:expr, $err:expr) => (
+        $crate::json_schema::validators::ValidationState{
+            errors: vec![
+                build_error!($dynamic_scope, $err)
+            ],
+            missing: vec![],
+            evaluated: ::std::collections::HashSet::new(),
+            annotations: vec![],
+            evaluations: vec![]
+        }
+    );
     ($err:expr) => (
         $crate::json_schema::validators::ValidationState{
             errors: vec![
                 Box::new($err)
             ],
-            missing: vec![]
+            missing: vec![],
+            evaluated: ::std::collections::HashSet::new(),
+            annotations: vec![],
+            evaluations: vec![]
         }
     )
 }
@@ -58,8 +90,14 @@ pub use self::dependencies::{Dependencies};
 pub use self::enum_::{Enum};
 pub use self::type_::{Type};
 pub use self::of::{AllOf, AnyOf, OneOf};
-pub use self::ref_::{Ref};
+pub use self::ref_::{Ref, RecursiveRef, DynamicRef};
 pub use self::not::{Not};
+pub use self::const_::{Const};
+pub use self::contains::{Contains};
+pub use self::property_names::{PropertyNames};
+pub use self::boolean_schema::{BooleanSchema};
+pub use self::if_::{If};
+pub use self::unevaluated::{UnevaluatedProperties, UnevaluatedItems};
 
 mod multiple_of;
 mod maxmin;
@@ -78,18 +116,42 @@ mod of;
 mod ref_;
 mod not;
 pub mod formats;
+mod const_;
+mod contains;
+mod property_names;
+mod boolean_schema;
+mod if_;
+mod unevaluated;
+
+/// Path of the property `key` of the instance at `path`. Instance paths are JSON
+/// pointers, so `~` and `/` are escaped in the key.
+pub fn property_path(path: &str, key: &str) -> String {
+    [path, error::escape_key(key).as_ref()].join("/")
+}
 
 #[derive(Debug)]
 pub struct ValidationState {
     pub errors: super::super::common::error::ValicoErrors,
-    pub missing: Vec<url::Url>
+    pub missing: Vec<url::Url>,
+    /// Names of the properties or indexes of the items of the instance that were
+    /// evaluated by applicator keywords, used by `unevaluatedProperties` and
+    /// `unevaluatedItems`. Only tracked since draft 2019-09.
+    pub evaluated: collections::HashSet<String>,
+    /// Annotations of the valid schemas, collected only by `ScopedSchema::annotate`.
+    pub annotations: Vec<Annotation>,
+    /// Every schema applied to the instance, recorded only by
+    /// `ScopedSchema::validate_verbose` for `OutputFormat::Verbose`.
+    pub evaluations: Vec<Evaluation>
 }
 
 impl ValidationState {
     pub fn new() -> ValidationState {
         ValidationState {
             errors: vec![],
-            missing: vec![]
+            missing: vec![],
+            evaluated: collections::HashSet::new(),
+            annotations: vec![],
+            evaluations: vec![]
         }
     }
 
@@ -101,9 +163,56 @@ impl ValidationState {
         self.errors.len() == 0 && self.missing.len() == 0
     }
 
+    /// Renders the errors with the messages of the catalog, as a list of objects with
+    /// the `code`, `path` and `message` of each error. The failed subschemas of
+    /// `anyOf`, `oneOf` and `propertyNames` are rendered the same way under `states`.
+    ///
+    /// The `{detail}` of an error is interpolated as the validator wrote it, in English.
+    pub fn localize(&self, catalog: &catalog::MessageCatalog) -> Value {
+        Value::Array(self.errors.iter().map(|error| {
+            let mut map = ::serde_json::Map::new();
+            map.insert("code".to_string(), to_value(error.get_code()).unwrap());
+            map.insert("path".to_string(), to_value(error.get_path()).unwrap());
+            map.insert("message".to_string(), to_value(catalog::localize(&**error, catalog)).unwrap());
+
+            let states = errors::nested_states(&**error);
+            if states.len() > 0 {
+                map.insert("states".to_string(), Value::Array(states.iter().map(|state| state.localize(catalog)).collect()));
+            }
+
+            Value::Object(map)
+        }).collect())
+    }
+
+    /// Renders the errors like compiler diagnostics, pointing at the values in
+    /// `source`, the JSON text of the validated instance read from the file `name`.
+    pub fn report(&self, source: &str, name: &str, style: super::report::Style) -> String {
+        super::report::render(source, name, self, style)
+    }
+
+    /// Renders the state in one of the standard output formats.
+    pub fn output(&self, format: super::output::OutputFormat) -> Value {
+        super::output::render(self, format)
+    }
+
     pub fn append(&mut self, second: ValidationState) {
         self.errors.extend(second.errors);
         self.missing.extend(second.missing);
+        self.evaluated.extend(second.evaluated);
+        self.annotations.extend(second.annotations);
+        self.evaluations.extend(second.evaluations);
+    }
+
+    /// Appends the state of a property or an item of the instance. What it evaluated
+    /// are the children of that property or item, not of the instance.
+    pub fn append_child(&mut self, mut child: ValidationState) {
+        child.evaluated.clear();
+        self.append(child);
+    }
+
+    /// The annotations that apply to the instance at the path.
+    pub fn annotations_at(&self, path: &str) -> Vec<&Annotation> {
+        self.annotations.iter().filter(|annotation| annotation.path == path).collect()
     }
 }
 
@@ -125,12 +234,268 @@ impl Serialize for ValidationState {
         map.insert("missing".to_string(), Value::Array(
             self.missing.iter().map(|url| to_value(&url.to_string()).unwrap()).collect::<Vec<Value>>()
         ));
+        if self.annotations.len() > 0 {
+            map.insert("annotations".to_string(), to_value(&self.annotations).unwrap());
+        }
         Value::Object(map).serialize(serializer)
     }
 }
 
+/// A schema applied to the instance at `path`, with the schemas it applied in turn.
+#[derive(Debug, Clone)]
+pub struct Evaluation {
+    pub path: String,
+    pub location: errors::KeywordLocation,
+    pub valid: bool,
+    pub evaluations: Vec<Evaluation>
+}
+
+/// Value of an annotation keyword of a schema that the instance at `path` is valid
+/// against.
+#[derive(Debug, Clone)]
+pub struct Annotation {
+    pub path: String,
+    pub keyword: String,
+    pub location: errors::KeywordLocation,
+    pub value: Value
+}
+
+impl Serialize for Annotation {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        let mut map = ::serde_json::Map::new();
+        map.insert("path".to_string(), to_value(&self.path).unwrap());
+        map.insert("keyword".to_string(), to_value(&self.keyword).unwrap());
+        map.insert("keyword_location".to_string(), to_value(error::to_pointer(&self.location.path)).unwrap());
+        match self.location.absolute {
+            Some(ref location) => { map.insert("absolute_keyword_location".to_string(), to_value(location).unwrap()); },
+            None => ()
+        }
+        map.insert("value".to_string(), self.value.clone());
+        Value::Object(map).serialize(serializer)
+    }
+}
+
+/// The chain of schema resources entered during validation, used to resolve
+/// `$recursiveRef` and `$dynamicRef`.
+#[derive(Debug, Clone, Copy)]
+pub struct DynamicScope<'a> {
+    resource: Option<&'a schema::Schema>,
+    parent: Option<&'a DynamicScope<'a>>,
+    /// Document of the schema being evaluated.
+    document: Option<&'a url::Url>,
+    /// Keyword location of the last reference that was followed and the length of the
+    /// fragment of its target, which the fragments below the target are relative to.
+    reference: (&'a [String], usize),
+    /// Number of references followed to get to the schema being evaluated.
+    references: usize,
+    annotate: bool,
+    /// Number of errors after which the validators stop.
+    max_errors: Option<usize>,
+    /// Errors found so far by the whole validation, shared by all the validators when
+    /// the number of errors is limited.
+    found: Option<&'a cell::Cell<usize>>,
+    /// Errors found before the current validator started.
+    errors_before: usize,
+    /// Only whether the instance is valid matters, so no errors are built.
+    flag: bool,
+    verbose: bool,
+    /// A schema being evaluated reads the properties and items evaluated by its
+    /// applicators, so they can't stop at the first valid subschema.
+    evaluated: bool
+}
+
+impl<'a> DynamicScope<'a> {
+    pub fn new() -> DynamicScope<'a> {
+        DynamicScope {
+            resource: None,
+            parent: None,
+            document: None,
+            reference: (&[], 0),
+            references: 0,
+            annotate: false,
+            max_errors: None,
+            found: None,
+            errors_before: 0,
+            flag: false,
+            verbose: false,
+            evaluated: false
+        }
+    }
+
+    /// Starts a validation that stops once `max_errors` errors are found, counting
+    /// them in `found`.
+    pub fn with_max_errors(max_errors: usize, found: &'a cell::Cell<usize>) -> DynamicScope<'a> {
+        DynamicScope {
+            max_errors: Some(max_errors),
+            found: Some(found),
+            ..DynamicScope::new()
+        }
+    }
+
+    /// Starts a validation that stops at the first error and doesn't build the
+    /// errors, counting them in `found`.
+    pub fn flag(found: &'a cell::Cell<usize>) -> DynamicScope<'a> {
+        DynamicScope {
+            flag: true,
+            ..DynamicScope::with_max_errors(1, found)
+        }
+    }
+
+    /// Whether the validation can stop, `state` being the errors collected by the
+    /// current validator so far.
+    pub fn reached_max_errors(&self, state: &ValidationState) -> bool {
+        match self.max_errors {
+            Some(max_errors) => self.count_errors(state) >= max_errors,
+            None => false
+        }
+    }
+
+    /// Sets the errors found by the whole validation to the ones found before the
+    /// current validator plus `state`, the errors it collected so far. Validators call
+    /// it before validating a subschema whose errors they may drop.
+    pub fn count_errors(&self, state: &ValidationState) -> usize {
+        match self.found {
+            Some(found) => {
+                let errors = self.errors_before + state.errors.len();
+                found.set(errors);
+                errors
+            },
+            None => 0
+        }
+    }
+
+    /// The scope of a validator that starts with the errors found so far.
+    pub fn resume(&self) -> DynamicScope<'a> {
+        DynamicScope {
+            errors_before: self.found.map(|found| found.get()).unwrap_or(0),
+            ..*self
+        }
+    }
+
+    /// Whether the validators build their errors, with the states of the failed
+    /// subschemas for the combinators.
+    pub fn builds_errors(&self) -> bool {
+        !self.flag
+    }
+
+    /// Enters a schema that reads the properties and items evaluated by its applicators.
+    pub fn track_evaluated(&self) -> DynamicScope<'a> {
+        DynamicScope {
+            evaluated: true,
+            ..*self
+        }
+    }
+
+    /// Whether the applicators have to find every property and item they evaluate.
+    pub fn tracks_evaluated(&self) -> bool {
+        self.evaluated
+    }
+
+    /// Starts a validation that collects the annotations of the valid schemas.
+    pub fn annotating() -> DynamicScope<'a> {
+        DynamicScope {
+            annotate: true,
+            ..DynamicScope::new()
+        }
+    }
+
+    pub fn collects_annotations(&self) -> bool {
+        self.annotate
+    }
+
+    /// Starts a validation that records every schema applied to the instance.
+    pub fn verbose() -> DynamicScope<'a> {
+        DynamicScope {
+            verbose: true,
+            ..DynamicScope::new()
+        }
+    }
+
+    pub fn records_evaluations(&self) -> bool {
+        self.verbose
+    }
+
+    /// Enters the schema resource.
+    pub fn push<'b>(&'b self, resource: &'b schema::Schema) -> DynamicScope<'b> {
+        DynamicScope {
+            resource: Some(resource),
+            parent: Some(self),
+            ..*self
+        }
+    }
+
+    /// Enters the schema at `fragment` of `document` through the reference keyword at
+    /// `keyword_location`.
+    pub fn follow<'b>(&'b self, keyword_location: &'b [String], document: &'b url::Url, fragment: &[String]) -> DynamicScope<'b> {
+        DynamicScope {
+            resource: None,
+            parent: Some(self),
+            document: Some(document),
+            reference: (keyword_location, fragment.len()),
+            // The validation enters its first document the same way
+            references: if self.document.is_some() { self.references + 1 } else { 0 },
+            ..*self
+        }
+    }
+
+    /// Number of references followed one inside the other to get here.
+    pub fn reference_depth(&self) -> usize {
+        self.references
+    }
+
+    pub fn document(&self) -> Option<&url::Url> {
+        self.document
+    }
+
+    /// Returns the first match of `f`, starting from the outermost resource.
+    pub fn find_outermost<T, F>(&self, f: &F) -> Option<T> where F: Fn(&'a schema::Schema) -> Option<T> {
+        self.parent.and_then(|parent| parent.find_outermost(f)).or_else(|| {
+            self.resource.and_then(|resource| f(resource))
+        })
+    }
+
+    /// Keys of the keyword at `fragment` of the current document, starting from the
+    /// schema the validation started at.
+    pub fn keyword_location(&self, fragment: &[String]) -> Vec<String> {
+        let (prefix, base) = self.reference;
+        prefix.iter().chain(fragment.iter().skip(base)).cloned().collect()
+    }
+
+    /// Location of the keyword of the schema at `fragment` of the current document.
+    pub fn locate_keyword(&self, fragment: &[String], keyword: &str) -> errors::KeywordLocation {
+        let mut fragment = fragment.to_vec();
+        fragment.push(keyword.to_string());
+        self.locate(&fragment)
+    }
+
+    /// Location of the keyword at `fragment` of the current document.
+    pub fn locate(&self, fragment: &[String]) -> errors::KeywordLocation {
+        errors::KeywordLocation {
+            path: self.keyword_location(fragment),
+            absolute: self.document.map(|document| {
+                let mut url = document.clone();
+                url.set_fragment(Some(&error::to_pointer(fragment)));
+                url.into_string()
+            })
+        }
+    }
+}
+
 pub trait Validator {
-    fn validate(&self, item: &Value, &str, &scope::Scope) -> ValidationState;
+    fn validate(&self, item: &Value, &str, &scope::Scope, &DynamicScope) -> ValidationState;
+
+    /// Validates the item knowing the state collected so far by the other validators of
+    /// the same schema. Validators that depend on what their siblings have evaluated
+    /// override this method.
+    fn validate_with_state(&self, item: &Value, path: &str, scope: &scope::Scope, dynamic_scope: &DynamicScope, _state: &ValidationState) -> ValidationState {
+        self.validate(item, path, scope, dynamic_scope)
+    }
+
+    /// Whether the validator reads what its siblings have evaluated, which the
+    /// applicators then have to record in full.
+    fn uses_evaluated(&self) -> bool {
+        false
+    }
 }
 
 impl fmt::Debug for Validator + 'static + Send + Sync {
@@ -142,8 +507,64 @@ impl fmt::Debug for Validator + 'static + Send + Sync {
 pub type BoxedValidator = Box<Validator + 'static + Send + Sync>;
 pub type Validators = Vec<BoxedValidator>;
 
-impl<T> Validator for T where T: Fn(&Value, &str, &scope::Scope) -> ValidationState {
-    fn validate(&self, val: &Value, path: &str, scope: &scope::Scope) -> ValidationState {
-        self(val, path, scope)
+impl<T> Validator for T where T: Fn(&Value, &str, &scope::Scope, &DynamicScope) -> ValidationState {
+    fn validate(&self, val: &Value, path: &str, scope: &scope::Scope, dynamic_scope: &DynamicScope) -> ValidationState {
+        self(val, path, scope, dynamic_scope)
     }
 }
+
+#[cfg(test)] use std::collections::HashMap;
+#[cfg(test)] use super::super::common::catalog::English;
+#[cfg(test)] use super::super::json_dsl;
+
+#[test]
+fn localize_state() {
+    let mut state = ValidationState::new();
+    state.errors.push(Box::new(errors::Required {
+        path: "/name".to_string(),
+        fragment: vec!["required".to_string()],
+        location: errors::KeywordLocation::default(),
+        property: "name".to_string()
+    }));
+    state.errors.push(Box::new(errors::Format {
+        path: "/date".to_string(),
+        fragment: vec!["format".to_string()],
+        location: errors::KeywordLocation::default(),
+        detail: "Malformed date".to_string(),
+        format: "date".to_string()
+    }));
+
+    let english = state.localize(&English);
+    assert_eq!(english[0]["message"], "The property name is required");
+    assert_eq!(english[1]["message"], "The value must be a valid date: Malformed date");
+
+    // Errors without the parameters of the message of their code fall back to
+    // their titles
+    let mut dsl_state = ValidationState::new();
+    dsl_state.errors.push(Box::new(json_dsl::errors::Required {
+        path: "/name".to_string(),
+        fragment: vec![]
+    }));
+    assert_eq!(dsl_state.localize(&English)[0]["message"], "This field is required");
+
+    let mut german = HashMap::new();
+    german.insert("required".to_string(), "Das Feld {property} ist erforderlich".to_string());
+    let messages = state.localize(&german);
+    assert_eq!(messages[0]["code"], "required");
+    assert_eq!(messages[0]["message"], "Das Feld name ist erforderlich");
+    // Codes missing from the catalog fall back to the English title
+    assert_eq!(messages[1]["message"], "Format is wrong");
+
+    // The failed branches of combinators are localized too
+    let mut any_of = ValidationState::new();
+    any_of.errors.push(Box::new(errors::AnyOf {
+        path: "".to_string(),
+        fragment: vec!["anyOf".to_string()],
+        location: errors::KeywordLocation::default(),
+        states: vec![state]
+    }));
+    let messages = any_of.localize(&german);
+    assert_eq!(messages[0]["message"], "AnyOf conditions are not met");
+    assert_eq!(messages[0]["states"][0][0]["message"], "Das Feld name ist erforderlich");
+    assert_eq!(messages[0]["states"][0][1]["message"], "Format is wrong");
+}
@@ -1,4 +1,4 @@
-use serde_json::{Value};
+use serde_json::{Number, Value};
 
 use super::super::errors;
 use super::super::scope;
@@ -7,12 +7,16 @@ use std::f64;
 #[allow(missing_copy_implementations)]
 pub struct MultipleOf {
     pub fragment: Vec<String>,
-    pub number: f64
+    pub keyword: &'static str,
+    pub number: f64,
+    /// The number as written in the schema, for the errors.
+    pub multiple_of: Number
 }
 
 impl super::Validator for MultipleOf {
-    fn validate(&self, val: &Value, path: &str, _scope: &scope::Scope) -> super::ValidationState {
-        let number = nonstrict_process!(val.as_f64(), path);
+    fn validate(&self, val: &Value, path: &str, _scope: &scope::Scope, dynamic_scope: &super::DynamicScope) -> super::ValidationState {
+        let actual = nonstrict_process!(match *val { Value::Number(ref number) => Some(number), _ => None }, path);
+        let number = actual.as_f64().unwrap();
 
         let valid = if (number.fract() == 0f64) && (self.number.fract() == 0f64) {
             (number % self.number) == 0f64
@@ -24,10 +28,13 @@ impl super::Validator for MultipleOf {
         if valid {
             super::ValidationState::new()
         } else {
-            val_error!(
+            val_error!(dynamic_scope,
                 errors::MultipleOf {
                     fragment: self.fragment.clone(),
-                    path: path.to_string()
+                    location: dynamic_scope.locate_keyword(&self.fragment, self.keyword),
+                    path: path.to_string(),
+                    multiple_of: self.multiple_of.clone(),
+                    actual: actual.clone()
                 }
             )
         }
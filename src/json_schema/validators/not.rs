@@ -1,31 +1,31 @@
 use serde_json::{Value};
-use url;
 
 use super::super::errors;
+use super::super::schema;
 use super::super::scope;
 
 #[allow(missing_copy_implementations)]
 pub struct Not {
     pub fragment: Vec<String>,
-    pub url: url::Url
+    pub keyword: &'static str,
+    pub link: schema::Link
 }
 
 impl super::Validator for Not {
-    fn validate(&self, val: &Value, path: &str, scope: &scope::Scope) -> super::ValidationState {
-        let schema = scope.resolve(&self.url);
+    fn validate(&self, val: &Value, path: &str, scope: &scope::Scope, dynamic_scope: &super::DynamicScope) -> super::ValidationState {
         let mut state = super::ValidationState::new();
 
-        if schema.is_some() {
-            if schema.unwrap().validate_in(val, path).is_valid() {
-                state.errors.push(Box::new(
-                    errors::Not {
-                        fragment: self.fragment.clone(),
-                        path: path.to_string()
-                    }
-                ))
-            }
-        } else {
-            state.missing.push(self.url.clone());
+        let mut not_state = self.link.get().validate_in_scope(val, path, scope, dynamic_scope);
+        state.evaluations.append(&mut not_state.evaluations);
+
+        if not_state.is_valid() {
+            state.errors.push(build_error!(dynamic_scope,
+                errors::Not {
+                    fragment: self.fragment.clone(),
+                    location: dynamic_scope.locate_keyword(&self.fragment, self.keyword),
+                    path: path.to_string()
+                }
+            ))
         }
 
         state
@@ -1,27 +1,25 @@
 use serde_json::{Value};
-use url;
+use std::collections;
 
 use super::super::errors;
+use super::super::schema;
 use super::super::scope;
 
 #[allow(missing_copy_implementations)]
 pub struct AllOf {
     pub fragment: Vec<String>,
-    pub schemes: Vec<url::Url>,
+    pub keyword: &'static str,
+    pub schemes: Vec<schema::Link>,
 }
 
 impl super::Validator for AllOf {
-    fn validate(&self, val: &Value, path: &str, scope: &scope::Scope) -> super::ValidationState {
+    fn validate(&self, val: &Value, path: &str, scope: &scope::Scope, dynamic_scope: &super::DynamicScope) -> super::ValidationState {
         let mut state = super::ValidationState::new();
 
-        for url in self.schemes.iter() {
-            let schema = scope.resolve(url);
+        for link in self.schemes.iter() {
+            if dynamic_scope.reached_max_errors(&state) { break; }
 
-            if schema.is_some() {
-                state.append(schema.unwrap().validate_in(val, path))
-            } else {
-                state.missing.push(url.clone())
-            }
+            state.append(link.get().validate_in_scope(val, path, scope, dynamic_scope))
         }
 
         state
@@ -31,38 +29,46 @@ impl super::Validator for AllOf {
 #[allow(missing_copy_implementations)]
 pub struct AnyOf {
     pub fragment: Vec<String>,
-    pub schemes: Vec<url::Url>,
+    pub keyword: &'static str,
+    pub schemes: Vec<schema::Link>,
+    /// Whether the evaluated properties and items of the branches are recorded for `unevaluated*`.
+    pub mark_evaluated: bool
 }
 
 impl super::Validator for AnyOf {
-    fn validate(&self, val: &Value, path: &str, scope: &scope::Scope) -> super::ValidationState {
+    fn validate(&self, val: &Value, path: &str, scope: &scope::Scope, dynamic_scope: &super::DynamicScope) -> super::ValidationState {
         let mut state = super::ValidationState::new();
 
         let mut states = vec![];
         let mut valid = false;
-        for url in self.schemes.iter() {
-            let schema = scope.resolve(url);
-
-            if schema.is_some() {
-                let current_state = schema.unwrap().validate_in(val, path);
-
-                state.missing.extend(current_state.missing.clone());
-
-                if current_state.is_valid() {
-                    valid = true;
+        for link in self.schemes.iter() {
+            // The errors of the branches are dropped
+            dynamic_scope.count_errors(&state);
+            let mut current_state = link.get().validate_in_scope(val, path, scope, dynamic_scope);
+
+            state.missing.extend(current_state.missing.clone());
+            state.evaluations.append(&mut current_state.evaluations);
+
+            if current_state.is_valid() {
+                valid = true;
+                state.evaluated.extend(current_state.evaluated);
+                state.annotations.extend(current_state.annotations);
+
+                // The remaining branches only matter when the properties and items they
+                // evaluate are read or their annotations are collected.
+                if !(self.mark_evaluated && dynamic_scope.tracks_evaluated()) && !dynamic_scope.collects_annotations() {
                     break;
-                } else {
-                   states.push(current_state)
                 }
-            } else {
-                state.missing.push(url.clone())
+            } else if dynamic_scope.builds_errors() {
+               states.push(current_state)
             }
         }
 
         if !valid {
-            state.errors.push(Box::new(
+            state.errors.push(build_error!(dynamic_scope,
                 errors::AnyOf {
                     fragment: self.fragment.clone(),
+                    location: dynamic_scope.locate_keyword(&self.fragment, self.keyword),
                     path: path.to_string(),
                     states: states
                 }
@@ -77,41 +83,47 @@ impl super::Validator for AnyOf {
 #[allow(missing_copy_implementations)]
 pub struct OneOf {
     pub fragment: Vec<String>,
-    pub schemes: Vec<url::Url>,
+    pub keyword: &'static str,
+    pub schemes: Vec<schema::Link>,
 }
 
 impl super::Validator for OneOf {
-    fn validate(&self, val: &Value, path: &str, scope: &scope::Scope) -> super::ValidationState {
+    fn validate(&self, val: &Value, path: &str, scope: &scope::Scope, dynamic_scope: &super::DynamicScope) -> super::ValidationState {
         let mut state = super::ValidationState::new();
 
         let mut states = vec![];
+        let mut evaluated = collections::HashSet::new();
+        let mut annotations = vec![];
         let mut valid = 0;
-        for url in self.schemes.iter() {
-            let schema = scope.resolve(url);
-
-            if schema.is_some() {
-                let current_state = schema.unwrap().validate_in(val, path);
-
-                state.missing.extend(current_state.missing.clone());
-
-                if current_state.is_valid() {
-                    valid += 1;
-                } else {
-                   states.push(current_state)
-                }
-            } else {
-                state.missing.push(url.clone())
+        for link in self.schemes.iter() {
+            // The errors of the branches are dropped
+            dynamic_scope.count_errors(&state);
+            let mut current_state = link.get().validate_in_scope(val, path, scope, dynamic_scope);
+
+            state.missing.extend(current_state.missing.clone());
+            state.evaluations.append(&mut current_state.evaluations);
+
+            if current_state.is_valid() {
+                valid += 1;
+                evaluated.extend(current_state.evaluated);
+                annotations.extend(current_state.annotations);
+            } else if dynamic_scope.builds_errors() {
+               states.push(current_state)
             }
         }
 
         if valid != 1 {
-            state.errors.push(Box::new(
+            state.errors.push(build_error!(dynamic_scope,
                 errors::OneOf {
                     fragment: self.fragment.clone(),
+                    location: dynamic_scope.locate_keyword(&self.fragment, self.keyword),
                     path: path.to_string(),
                     states: states
                 }
             ))
+        } else {
+            state.evaluated = evaluated;
+            state.annotations = annotations;
         }
 
 
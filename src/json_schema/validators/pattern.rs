@@ -7,20 +7,23 @@ use super::super::scope;
 #[allow(missing_copy_implementations)]
 pub struct Pattern {
     pub fragment: Vec<String>,
+    pub keyword: &'static str,
     pub regex: regex::Regex
 }
 
 impl super::Validator for Pattern {
-    fn validate(&self, val: &Value, path: &str, _scope: &scope::Scope) -> super::ValidationState {
+    fn validate(&self, val: &Value, path: &str, _scope: &scope::Scope, dynamic_scope: &super::DynamicScope) -> super::ValidationState {
         let string = nonstrict_process!(val.as_str(), path);
 
         if self.regex.is_match(string) {
             super::ValidationState::new()
         } else {
-            val_error!(
+            val_error!(dynamic_scope,
                 errors::Pattern {
                     fragment: self.fragment.clone(),
-                    path: path.to_string()
+                    location: dynamic_scope.locate_keyword(&self.fragment, self.keyword),
+                    path: path.to_string(),
+                    pattern: self.regex.as_str().to_string()
                 }
             )
         }
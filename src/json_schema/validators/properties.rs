@@ -1,85 +1,85 @@
 use serde_json::{Value};
 use regex;
 use std::collections;
-use url;
 
 use super::super::errors;
+use super::super::schema;
 use super::super::scope;
 
 #[derive(Debug)]
 pub enum AdditionalKind {
     Boolean(bool),
-    Schema(url::Url)
+    Schema(schema::Link)
 }
 
 #[allow(missing_copy_implementations)]
 pub struct Properties {
     pub fragment: Vec<String>,
-    pub properties: collections::HashMap<String, url::Url>,
-    pub additional: AdditionalKind,
-    pub patterns: Vec<(regex::Regex, url::Url)>
+    pub keyword: &'static str,
+    pub properties: collections::HashMap<String, schema::Link>,
+    pub additional: Option<AdditionalKind>,
+    pub patterns: Vec<(regex::Regex, schema::Link)>,
+    /// Whether the evaluated properties are recorded for `unevaluatedProperties`.
+    pub mark_evaluated: bool
 }
 
 impl super::Validator for Properties {
-    fn validate(&self, val: &Value, path: &str, scope: &scope::Scope) -> super::ValidationState {
+    fn validate(&self, val: &Value, path: &str, scope: &scope::Scope, dynamic_scope: &super::DynamicScope) -> super::ValidationState {
         let object = nonstrict_process!(val.as_object(), path);
         let mut state = super::ValidationState::new();
 
         'main: for (key, value) in object.iter() {
+            if dynamic_scope.reached_max_errors(&state) { break; }
+
+            let value_path = super::property_path(path, key);
+
             let mut is_property_passed = false;
             if self.properties.contains_key(key) {
-                let url = self.properties.get(key).unwrap();
-                let schema = scope.resolve(url);
-                if schema.is_some() {
-                    let value_path = [path, key.as_ref()].join("/");
-                    state.append(schema.unwrap().validate_in(value, value_path.as_ref()))
-                } else {
-                    state.missing.push(url.clone())
-                }
+                let link = self.properties.get(key).unwrap();
+                state.append_child(link.get().validate_in_scope(value, value_path.as_ref(), scope, dynamic_scope));
 
                is_property_passed = true;
             }
 
             let mut is_pattern_passed = false;
-            for &(ref regex, ref url) in self.patterns.iter() {
+            for &(ref regex, ref link) in self.patterns.iter() {
                 if regex.is_match(key.as_ref()) {
-                    let schema = scope.resolve(url);
-                    if schema.is_some() {
-                        let value_path = [path, key.as_ref()].join("/");
-                        state.append(schema.unwrap().validate_in(value, value_path.as_ref()));
-                        is_pattern_passed = true;
-                    } else {
-                        state.missing.push(url.clone())
-                    }
+                    state.append_child(link.get().validate_in_scope(value, value_path.as_ref(), scope, dynamic_scope));
+                    is_pattern_passed = true;
                 }
             }
 
             if is_property_passed || is_pattern_passed {
+                if self.mark_evaluated {
+                    state.evaluated.insert(key.clone());
+                }
                 continue 'main;
             }
 
             match self.additional {
-                AdditionalKind::Boolean(allowed) if allowed == false => {
-                    state.errors.push(Box::new(
+                Some(AdditionalKind::Boolean(allowed)) if allowed == false => {
+                    state.errors.push(build_error!(dynamic_scope,
                         errors::Properties {
                             fragment: self.fragment.clone(),
+                            location: dynamic_scope.locate_keyword(&self.fragment, self.keyword),
                             path: path.to_string(),
                             detail: "Additional properties are not allowed".to_string()
                         }
                     ))
                 },
-                AdditionalKind::Schema(ref url) => {
-                    let schema = scope.resolve(url);
-
-                    if schema.is_some() {
-                        let value_path = [path, key.as_ref()].join("/");
-                        state.append(schema.unwrap().validate_in(value, value_path.as_ref()))
-                    } else {
-                        state.missing.push(url.clone())
+                Some(AdditionalKind::Schema(ref link)) => {
+                    state.append_child(link.get().validate_in_scope(value, value_path.as_ref(), scope, dynamic_scope));
+                    if self.mark_evaluated {
+                        state.evaluated.insert(key.clone());
+                    }
+                },
+                Some(AdditionalKind::Boolean(_)) => {
+                    if self.mark_evaluated {
+                        state.evaluated.insert(key.clone());
                     }
                 },
                 // Additional are allowed here
-                _ => ()
+                None => ()
             }
         }
 
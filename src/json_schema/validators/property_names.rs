@@ -0,0 +1,45 @@
+use serde_json::{Value};
+
+use super::super::errors;
+use super::super::schema;
+use super::super::scope;
+
+#[allow(missing_copy_implementations)]
+pub struct PropertyNames {
+    pub fragment: Vec<String>,
+    pub keyword: &'static str,
+    pub link: schema::Link
+}
+
+impl super::Validator for PropertyNames {
+    fn validate(&self, val: &Value, path: &str, scope: &scope::Scope, dynamic_scope: &super::DynamicScope) -> super::ValidationState {
+        let object = nonstrict_process!(val.as_object(), path);
+
+        let schema = self.link.get();
+        let mut state = super::ValidationState::new();
+
+        for key in object.keys() {
+            if dynamic_scope.reached_max_errors(&state) { break; }
+
+            let key_path = super::property_path(path, key);
+            let mut key_state = schema.validate_in_scope(&Value::String(key.clone()), key_path.as_ref(), scope, dynamic_scope);
+
+            state.missing.extend(key_state.missing.clone());
+            state.evaluations.append(&mut key_state.evaluations);
+
+            if !key_state.is_valid() {
+                state.errors.push(build_error!(dynamic_scope,
+                    errors::PropertyNames {
+                        fragment: self.fragment.clone(),
+                        location: dynamic_scope.locate_keyword(&self.fragment, self.keyword),
+                        path: key_path,
+                        detail: format!("Property name `{}` is not valid", key),
+                        states: if dynamic_scope.builds_errors() { vec![key_state] } else { vec![] }
+                    }
+                ))
+            }
+        }
+
+        state
+    }
+}
@@ -1,24 +1,72 @@
 use serde_json::{Value};
-use url;
 
+use super::super::schema;
 use super::super::scope;
 
 #[allow(missing_copy_implementations)]
 pub struct Ref {
     pub fragment: Vec<String>,
-    pub url: url::Url
+    pub keyword: &'static str,
+    pub link: schema::Link
 }
 
 impl super::Validator for Ref {
-    fn validate(&self, val: &Value, path: &str, scope: &scope::Scope) -> super::ValidationState {
-        let schema = scope.resolve(&self.url);
-
-        if schema.is_some() {
-            schema.unwrap().validate_in(val, path)
-        } else {
-            let mut state = super::ValidationState::new();
-            state.missing.push(self.url.clone());
-            state
+    fn validate(&self, val: &Value, path: &str, scope: &scope::Scope, dynamic_scope: &super::DynamicScope) -> super::ValidationState {
+        self.link.get().validate_reference(val, path, scope, dynamic_scope, &self.fragment, self.keyword)
+    }
+}
+
+#[allow(missing_copy_implementations)]
+pub struct RecursiveRef {
+    pub fragment: Vec<String>,
+    pub keyword: &'static str,
+    pub link: schema::Link
+}
+
+impl super::Validator for RecursiveRef {
+    fn validate(&self, val: &Value, path: &str, scope: &scope::Scope, dynamic_scope: &super::DynamicScope) -> super::ValidationState {
+        let schema = self.link.get();
+
+        if schema.recursive_anchor() {
+            // The outermost resource that is also a recursive anchor wins
+            let outermost = dynamic_scope.find_outermost(&|resource| {
+                if resource.recursive_anchor() { Some(resource) } else { None }
+            });
+
+            if let Some(outermost) = outermost {
+                return outermost.validate_reference(val, path, scope, dynamic_scope, &self.fragment, self.keyword)
+            }
         }
+
+        schema.validate_reference(val, path, scope, dynamic_scope, &self.fragment, self.keyword)
+    }
+}
+
+#[allow(missing_copy_implementations)]
+pub struct DynamicRef {
+    pub fragment: Vec<String>,
+    pub keyword: &'static str,
+    pub link: schema::Link,
+    pub anchor: Option<String>
+}
+
+impl super::Validator for DynamicRef {
+    fn validate(&self, val: &Value, path: &str, scope: &scope::Scope, dynamic_scope: &super::DynamicScope) -> super::ValidationState {
+        let schema = self.link.get();
+
+        match self.anchor {
+            // Behaves like `$ref` unless the initial target is a dynamic anchor with the same name
+            Some(ref anchor) if schema.dynamic_anchor() == Some(anchor.as_ref()) => {
+                // The targets of the anchors of each resource are linked at compile time
+                let outermost = dynamic_scope.find_outermost(&|resource| resource.dynamic_anchor_target(anchor));
+
+                if let Some(outermost) = outermost {
+                    return outermost.validate_reference(val, path, scope, dynamic_scope, &self.fragment, self.keyword)
+                }
+            },
+            _ => ()
+        }
+
+        schema.validate_reference(val, path, scope, dynamic_scope, &self.fragment, self.keyword)
     }
 }
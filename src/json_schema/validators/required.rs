@@ -6,20 +6,25 @@ use super::super::scope;
 #[allow(missing_copy_implementations)]
 pub struct Required {
     pub fragment: Vec<String>,
+    pub keyword: &'static str,
     pub items: Vec<String>
 }
 
 impl super::Validator for Required {
-    fn validate(&self, val: &Value, path: &str, _scope: &scope::Scope) -> super::ValidationState {
+    fn validate(&self, val: &Value, path: &str, _scope: &scope::Scope, dynamic_scope: &super::DynamicScope) -> super::ValidationState {
         let object = nonstrict_process!(val.as_object(), path);
         let mut state = super::ValidationState::new();
 
         for key in self.items.iter() {
+            if dynamic_scope.reached_max_errors(&state) { break; }
+
             if !object.contains_key(key) {
-                state.errors.push(Box::new(
+                state.errors.push(build_error!(dynamic_scope,
                     errors::Required {
                         fragment: self.fragment.clone(),
-                        path: [path, key.as_ref()].join("/")
+                        location: dynamic_scope.locate_keyword(&self.fragment, self.keyword),
+                        path: super::property_path(path, key),
+                        property: key.clone()
                     }
                 ))
             }
@@ -14,6 +14,7 @@ pub enum TypeKind {
 #[allow(missing_copy_implementations)]
 pub struct Type {
     pub fragment: Vec<String>,
+    pub keyword: &'static str,
     pub item: TypeKind
 }
 
@@ -29,18 +30,34 @@ fn check_type(val: &Value, ty: &json_schema::PrimitiveType) -> bool {
     }
 }
 
+/// Name of the most specific primitive type of the value.
+pub fn type_of(val: &Value) -> &'static str {
+    match *val {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) if val.is_u64() || val.is_i64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object"
+    }
+}
+
 impl super::Validator for Type {
-    fn validate(&self, val: &Value, path: &str, _scope: &scope::Scope) -> super::ValidationState {
+    fn validate(&self, val: &Value, path: &str, _scope: &scope::Scope, dynamic_scope: &super::DynamicScope) -> super::ValidationState {
         let mut state = super::ValidationState::new();
 
         match self.item {
             TypeKind::Single(ref t) => {
                 if !check_type(val, t) {
-                    state.errors.push(Box::new(
+                    state.errors.push(build_error!(dynamic_scope,
                         errors::WrongType {
                             fragment: self.fragment.clone(),
+                            location: dynamic_scope.locate_keyword(&self.fragment, self.keyword),
                             path: path.to_string(),
-                            detail: format!("The value must be {}", t)
+                            detail: format!("The value must be {}", t),
+                            expected: vec![t.to_string()],
+                            actual: type_of(val).to_string()
                         }
                     ))
                 }
@@ -55,11 +72,14 @@ impl super::Validator for Type {
                 }
 
                 if !is_type_match {
-                    state.errors.push(Box::new(
+                    state.errors.push(build_error!(dynamic_scope,
                         errors::WrongType {
                             fragment: self.fragment.clone(),
+                            location: dynamic_scope.locate_keyword(&self.fragment, self.keyword),
                             path: path.to_string(),
-                            detail: format!("The value must be any of: {}", set.iter().map(|ty| ty.to_string()).collect::<Vec<String>>().join(", "))
+                            detail: format!("The value must be any of: {}", set.iter().map(|ty| ty.to_string()).collect::<Vec<String>>().join(", ")),
+                            expected: set.iter().map(|ty| ty.to_string()).collect(),
+                            actual: type_of(val).to_string()
                         }
                     ))
                 }
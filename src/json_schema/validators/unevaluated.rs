@@ -0,0 +1,110 @@
+use serde_json::{Value};
+
+use super::super::errors;
+use super::super::scope;
+use super::properties;
+use super::items;
+
+#[allow(missing_copy_implementations)]
+pub struct UnevaluatedProperties {
+    pub fragment: Vec<String>,
+    pub keyword: &'static str,
+    pub unevaluated: properties::AdditionalKind
+}
+
+impl super::Validator for UnevaluatedProperties {
+    fn validate(&self, val: &Value, path: &str, scope: &scope::Scope, dynamic_scope: &super::DynamicScope) -> super::ValidationState {
+        self.validate_with_state(val, path, scope, dynamic_scope, &super::ValidationState::new())
+    }
+
+    fn uses_evaluated(&self) -> bool {
+        true
+    }
+
+    fn validate_with_state(&self, val: &Value, path: &str, scope: &scope::Scope, dynamic_scope: &super::DynamicScope, evaluated: &super::ValidationState) -> super::ValidationState {
+        let object = nonstrict_process!(val.as_object(), path);
+        let mut state = super::ValidationState::new();
+
+        for (key, value) in object.iter() {
+            if dynamic_scope.reached_max_errors(&state) { break; }
+
+            if evaluated.evaluated.contains(key) {
+                continue;
+            }
+
+            match self.unevaluated {
+                properties::AdditionalKind::Boolean(false) => {
+                    state.errors.push(build_error!(dynamic_scope,
+                        errors::Properties {
+                            fragment: self.fragment.clone(),
+                            location: dynamic_scope.locate_keyword(&self.fragment, self.keyword),
+                            path: path.to_string(),
+                            detail: "Unevaluated properties are not allowed".to_string()
+                        }
+                    ))
+                },
+                properties::AdditionalKind::Schema(ref link) => {
+                    let value_path = super::property_path(path, key);
+                    state.append_child(link.get().validate_in_scope(value, value_path.as_ref(), scope, dynamic_scope))
+                },
+                properties::AdditionalKind::Boolean(true) => ()
+            }
+
+            state.evaluated.insert(key.clone());
+        }
+
+        state
+    }
+}
+
+#[allow(missing_copy_implementations)]
+pub struct UnevaluatedItems {
+    pub fragment: Vec<String>,
+    pub keyword: &'static str,
+    pub unevaluated: items::AdditionalKind
+}
+
+impl super::Validator for UnevaluatedItems {
+    fn validate(&self, val: &Value, path: &str, scope: &scope::Scope, dynamic_scope: &super::DynamicScope) -> super::ValidationState {
+        self.validate_with_state(val, path, scope, dynamic_scope, &super::ValidationState::new())
+    }
+
+    fn uses_evaluated(&self) -> bool {
+        true
+    }
+
+    fn validate_with_state(&self, val: &Value, path: &str, scope: &scope::Scope, dynamic_scope: &super::DynamicScope, evaluated: &super::ValidationState) -> super::ValidationState {
+        let array = nonstrict_process!(val.as_array(), path);
+        let mut state = super::ValidationState::new();
+
+        for (idx, item) in array.iter().enumerate() {
+            if dynamic_scope.reached_max_errors(&state) { break; }
+
+            if evaluated.evaluated.contains(&idx.to_string()) {
+                continue;
+            }
+
+            match self.unevaluated {
+                items::AdditionalKind::Boolean(false) => {
+                    state.errors.push(build_error!(dynamic_scope,
+                        errors::Items {
+                            fragment: self.fragment.clone(),
+                            location: dynamic_scope.locate_keyword(&self.fragment, self.keyword),
+                            path: path.to_string(),
+                            detail: "Unevaluated items are not allowed".to_string()
+                        }
+                    ))
+                },
+                items::AdditionalKind::Schema(ref link) => {
+                    let item_path = [path, idx.to_string().as_ref()].join("/");
+                    state.append_child(link.get().validate_in_scope(item, item_path.as_ref(), scope, dynamic_scope))
+                },
+                items::AdditionalKind::Boolean(true) => ()
+            }
+
+            state.evaluated.insert(idx.to_string());
+        }
+
+        state
+    }
+}
@@ -1,15 +1,17 @@
 use serde_json::{Value};
 
 use super::super::errors;
+use super::super::helpers;
 use super::super::scope;
 
 #[allow(missing_copy_implementations)]
 pub struct UniqueItems {
     pub fragment: Vec<String>,
+    pub keyword: &'static str,
 }
 
 impl super::Validator for UniqueItems {
-    fn validate(&self, val: &Value, path: &str, _scope: &scope::Scope) -> super::ValidationState {
+    fn validate(&self, val: &Value, path: &str, _scope: &scope::Scope, dynamic_scope: &super::DynamicScope) -> super::ValidationState {
         let array = nonstrict_process!(val.as_array(), path);
 
         // TODO we need some quicker algorithm for this
@@ -17,14 +19,14 @@ impl super::Validator for UniqueItems {
         let mut unique = true;
         'main: for (idx, item_i) in array.iter().enumerate() {
             for item_j in array[..idx].iter() {
-                if item_i == item_j {
+                if helpers::is_equal(item_i, item_j) {
                     unique = false;
                     break 'main;
                 }
             }
 
             for item_j in array[(idx + 1)..].iter() {
-                if item_i == item_j {
+                if helpers::is_equal(item_i, item_j) {
                     unique = false;
                     break 'main;
                 }
@@ -34,9 +36,10 @@ impl super::Validator for UniqueItems {
         if unique {
             super::ValidationState::new()
         } else {
-            val_error!(
+            val_error!(dynamic_scope,
                 errors::UniqueItems {
                     fragment: self.fragment.clone(),
+                    location: dynamic_scope.locate_keyword(&self.fragment, self.keyword),
                     path: path.to_string()
                 }
             )
@@ -14,3 +14,4 @@ pub mod json_dsl;
 pub mod json_schema;
 
 pub use common::error::{ValicoErrors};
+pub use common::catalog::{MessageCatalog, English};
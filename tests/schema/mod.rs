@@ -2,6 +2,7 @@ use std::fs;
 use std::path;
 use std::io::Read;
 use serde_json::{Value, from_str, to_value, to_string_pretty};
+use url;
 use valico::json_schema;
 
 fn visit_specs<F>(dir: &path::Path, cb: F) where F: Fn(&path::Path, Value) {
@@ -25,30 +26,68 @@ fn visit_specs<F>(dir: &path::Path, cb: F) where F: Fn(&path::Path, Value) {
 
 #[test]
 fn test_suite() {
-    let mut content = String::new();
+    // String lengths are counted in bytes instead of code points
+    let exceptions: Vec<(String, String)> = vec![
+        ("maxLength.json".to_string(), "two supplementary Unicode code points is long enough".to_string()),
+        ("minLength.json".to_string(), "one supplementary Unicode code point is not long enough".to_string()),
+    ];
 
-    fs::File::open(&path::Path::new("tests/schema/schema.json")).ok().unwrap()
-        .read_to_string(&mut content).ok().unwrap();
+    run_specs("tests/schema/JSON-Schema-Test-Suite/tests/draft4", json_schema::Draft::V4, None, &exceptions);
+    run_specs("tests/schema/JSON-Schema-Test-Suite/tests/draft6", json_schema::Draft::V6, None, &exceptions);
+    run_specs("tests/schema/JSON-Schema-Test-Suite/tests/draft7", json_schema::Draft::V7, None, &exceptions);
 
-    let json_v4_schema: Value = from_str(&content).unwrap();
+    // Vocabularies declared by custom meta-schemas are not honoured, so the
+    // validation keywords apply under a meta-schema without them
+    let mut exceptions = exceptions;
+    exceptions.push(("vocabulary.json".to_string(), "no validation: invalid number, but it still validates".to_string()));
 
-    visit_specs(&path::Path::new("tests/schema/JSON-Schema-Test-Suite/tests/draft4"), |path, spec_set: Value| {
-        let spec_set = spec_set.as_array().unwrap();
+    // Runs every file, including the unevaluatedProperties, unevaluatedItems,
+    // $recursiveRef and $dynamicRef suites
+    run_specs("tests/schema/JSON-Schema-Test-Suite/tests/draft2019-09", json_schema::Draft::V2019_09, None, &exceptions);
+    run_specs("tests/schema/JSON-Schema-Test-Suite/tests/draft2020-12", json_schema::Draft::V2020_12, None, &exceptions);
+}
+
+#[test]
+fn test_suite_formats() {
+    run_specs("tests/schema/JSON-Schema-Test-Suite/tests/draft4/optional/format", json_schema::Draft::V4, None, &[]);
+    // The optional format tests expect the formats to be asserted
+    run_specs("tests/schema/JSON-Schema-Test-Suite/tests/draft2020-12/optional/format", json_schema::Draft::V2020_12,
+        Some(json_schema::FormatMode::Assert), &[]);
+}
+
+/// The documents of the suite's `remotes` directory, which the specs expect to be
+/// served at `http://localhost:1234/`.
+fn remotes() -> Vec<(url::Url, Value)> {
+    let mut errors = vec![];
+    let files = json_schema::loader::list_dir(&path::Path::new("tests/schema/JSON-Schema-Test-Suite/remotes"),
+        &url::Url::parse("http://localhost:1234/").unwrap(), &mut errors);
+    assert!(errors.is_empty(), "Error in remotes: {:?}", errors);
 
-        let exceptions: Vec<(String, String)> = vec![
-            ("maxLength.json".to_string(), "two supplementary Unicode code points is long enough".to_string()),
-            ("minLength.json".to_string(), "one supplementary Unicode code point is not long enough".to_string()),
-            ("refRemote.json".to_string(), "remote ref invalid".to_string()),
-            ("refRemote.json".to_string(), "remote fragment invalid".to_string()),
-            ("refRemote.json".to_string(), "ref within ref invalid".to_string()),
-            ("refRemote.json".to_string(), "changed scope ref invalid".to_string()),
-        ];
+    files.into_iter().map(|(path, url)| {
+        (url, json_schema::loader::read_file(&path).unwrap())
+    }).collect()
+}
+
+fn run_specs(dir: &str, draft: json_schema::Draft, format_mode: Option<json_schema::FormatMode>, exceptions: &[(String, String)]) {
+    let remotes = remotes();
+
+    visit_specs(&path::Path::new(dir), |path, spec_set: Value| {
+        let spec_set = spec_set.as_array().unwrap();
 
         for spec in spec_set.iter() {
             let spec = spec.as_object().unwrap();
             let mut scope = json_schema::Scope::new();
+            scope.set_default_draft(draft);
+            if let Some(format_mode) = format_mode {
+                scope.set_format_mode(format_mode);
+            }
 
-            scope.compile(json_v4_schema.clone(), true).ok().unwrap();
+            let mut loader = json_schema::MemoryLoader::new();
+            for &(ref url, ref remote) in remotes.iter() {
+                loader.insert(url, remote.clone());
+            }
+            scope.add_loader(loader);
+            scope.add_loader(json_schema::meta_schema::load);
 
             let spec_desc = spec.get("description").map(|v| v.as_str().unwrap()).unwrap_or("");
 
@@ -72,7 +111,7 @@ fn test_suite() {
                 let state = schema.validate(&data);
 
                 if state.is_valid() != valid {
-                    if !&exceptions[..].contains(&(path.file_name().unwrap().to_str().unwrap().to_string(), description.to_string())) {
+                    if !exceptions.contains(&(path.file_name().unwrap().to_str().unwrap().to_string(), description.to_string())) {
                         panic!("Failure: \"{}\" in \"{}\" -> \"{}\" with state: \n {}",
                             path.file_name().unwrap().to_str().unwrap(),
                             spec_desc,
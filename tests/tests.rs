@@ -2,6 +2,7 @@ extern crate valico;
 extern crate regex;
 extern crate serde;
 extern crate serde_json;
+extern crate url;
 
 mod dsl;
 mod schema;